@@ -83,7 +83,7 @@ impl AABB {
     }
 
     pub fn longest_axis(&self) -> usize {
-        #[expect(clippy::collapsible_else_if, reason = "readability; both branches should look the same")]
+        #[allow(clippy::collapsible_else_if, reason = "readability; both branches should look the same")]
         if self.x.size() > self.y.size() {
             if self.x.size() > self.z.size() { 0 } else { 2 }
         } else {
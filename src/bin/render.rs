@@ -0,0 +1,516 @@
+use std::num::NonZeroU32;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+use image::{ImageError, Rgb32FImage};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use one_weekend_raytracer::camera::control::{RenderBudget, RenderControl, StopReason};
+use one_weekend_raytracer::integrator;
+use one_weekend_raytracer::output::{self, OutputFormat};
+use one_weekend_raytracer::output::denoise::{DenoiseFeatures, Denoiser};
+use one_weekend_raytracer::prelude::*;
+use one_weekend_raytracer::scene::builtin;
+use one_weekend_raytracer::util::timer::ScopedTimer;
+
+const USAGE: &str = "\
+Usage: render <SCENE> [OPTIONS]
+
+<SCENE> is either the name of a built-in scene, or the path to a scene file.
+
+Options:
+  -o, --output <PATH>        Output image path [default: <scene>.png]
+      --format <FORMAT>      Output image format, e.g. exr, hdr, pfm, png, jpeg [default: from output extension]
+  -r, --resolution <WxH>     Image resolution, e.g. 1920x1080
+  -w, --width <N>            Image width, keeping the scene's aspect ratio
+  -s, --samples <N>          Samples per pixel
+  -d, --depth <N>            Maximum ray bounce depth
+      --min-depth <N>        Bounces before russian roulette may terminate a path
+      --adaptive <ERROR>     Stop sampling pixels once their relative error is below ERROR, e.g. 0.05
+      --min-samples <N>      Samples every pixel gets before it may stop when sampling adaptively
+      --sampler <NAME>       Sample sequence: independent, stratified, halton, sobol, blue_noise [default: sobol]
+      --filter <NAME>        Pixel reconstruction filter: box, tent, gaussian, mitchell, lanczos [default: box]
+      --filter-radius <R>    Filter radius in pixels [default: depends on the filter]
+      --filter-mode <MODE>   Sample positions drawn from the filter, or splatted to all pixels in reach: importance, splat
+      --aov <LIST>           Also save render passes, comma separated or 'all': depth, normal, albedo, uv, object_id,
+                             material_id, emission, direct, indirect, sample_count; as layers of .exr output,
+                             otherwise as <output>.<pass>.<extension>
+      --denoise              Denoise the image, guided by the albedo, normal and depth of the first hits
+      --sample-map <PATH>    Also save an image of how many samples each pixel took
+      --tonemap <OP>         Tone mapping for 8-bit output: clamp, reinhard, extended_reinhard, aces, hable
+      --exposure <EV>        Exposure adjustment in stops
+      --white <N>            White point for the tone mapping operator
+  -i, --integrator <NAME>    Light transport algorithm: path, naive, direct, ao, normals [default: path]
+      --progressive <N>      Render in passes of N samples per pixel, saving the output after each pass
+      --snapshot-interval <SECS>
+                             With --progressive, save the output at most this often
+      --time-limit <SECS>    Stop rendering after this long, keeping what has been rendered so far
+      --sample-limit <N>     Stop rendering before the total number of samples exceeds N
+      --noise <ERROR>        With --progressive, stop once the mean relative error drops below ERROR, e.g. 0.02
+  -j, --threads <N>          Number of render threads [default: all cores]
+      --seed <N>             Seed for the render's sampling and randomly generated scene contents
+      --tile-size <N>        Size of the square tiles the image is rendered in [default: 32]
+      --tile-order <ORDER>   Order tiles are rendered in: scanline, spiral, hilbert [default: scanline]
+      --crop <X,Y,W,H>       Only render the given pixel region
+  -q, --quiet                Don't print progress
+  -l, --list                 List the built-in scenes
+  -h, --help                 Print this message";
+
+struct Args {
+    scene: String,
+    output: Option<PathBuf>,
+    format: Option<OutputFormat>,
+    resolution: Option<(NonZeroU32, NonZeroU32)>,
+    width: Option<NonZeroU32>,
+    samples: Option<NonZeroU32>,
+    depth: Option<u16>,
+    min_depth: Option<u16>,
+    adaptive_error: Option<f32>,
+    min_samples: Option<u32>,
+    sampler: Option<SamplerKind>,
+    filter: Option<FilterKind>,
+    filter_radius: Option<f32>,
+    filter_mode: Option<FilterMode>,
+    aovs: Option<AovSet>,
+    denoise: bool,
+    sample_map: Option<PathBuf>,
+    integrator: integrator::IntegratorBuilder,
+    tone_map: Option<ToneMapOperator>,
+    exposure: Option<f32>,
+    white_point: Option<f32>,
+    progressive: Option<NonZeroU32>,
+    snapshot_interval: Option<Duration>,
+    budget: RenderBudget,
+    threads: Option<usize>,
+    seed: Option<u64>,
+    crop: Option<PixelRegion>,
+    tile_size: Option<NonZeroU32>,
+    tile_order: Option<TileOrder>,
+    quiet: bool,
+}
+
+enum Command {
+    Render(Box<Args>),
+    List,
+    Help,
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Render(args)) => match render(*args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        Ok(Command::List) => {
+            builtin::BUILTIN_SCENES.iter().for_each(|(name, _)| println!("{name}"));
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn render(args: Args) -> Result<(), String> {
+    if let Some(threads) = args.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|err| format!("failed to create thread pool: {err}"))?;
+    }
+
+    let mut rng = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+
+    let (mut scene, name) = match builtin::find(&args.scene) {
+        Some(builder) => (builder(&mut rng), args.scene.clone()),
+        None => {
+            let path = Path::new(&args.scene);
+
+            let scene = Scene::load(path)
+                .map_err(|err| format!("failed to load scene '{}': {err}", path.display()))?;
+
+            let name = path.file_stem()
+                .map_or_else(|| "render".to_string(), |stem| stem.to_string_lossy().into_owned());
+
+            (scene, name)
+        }
+    };
+
+    if let Some((width, height)) = args.resolution {
+        scene.camera.dimensions = CameraDimensions { width, height };
+    } else if let Some(width) = args.width {
+        scene.camera.dimensions = CameraDimensions::from_aspect_ratio(width, scene.camera.dimensions.aspect_ratio());
+    }
+
+    if let Some(samples) = args.samples {
+        scene.camera.render_quality.samples_per_pixel = samples.get();
+    }
+
+    if let Some(depth) = args.depth {
+        scene.camera.render_quality.max_depth = depth;
+    }
+
+    if let Some(min_depth) = args.min_depth {
+        scene.camera.render_quality.min_depth = min_depth;
+    }
+
+    if let Some(seed) = args.seed {
+        scene.camera.render_quality.seed = seed;
+    }
+
+    if let Some(sampler) = args.sampler {
+        scene.camera.render_quality.sampler = sampler;
+    }
+
+    if let Some(kind) = args.filter {
+        scene.camera.filter.kind = kind;
+        scene.camera.filter.radius = kind.default_radius();
+    }
+
+    if let Some(radius) = args.filter_radius {
+        scene.camera.filter.radius = radius;
+    }
+
+    if let Some(mode) = args.filter_mode {
+        scene.camera.filter.mode = mode;
+    }
+
+    if let Some(aovs) = args.aovs {
+        scene.camera.aovs = aovs;
+    }
+
+    // the denoiser's features are rendered as AOVs, but only saved if they were asked for
+    let aovs = scene.camera.aovs;
+    let denoiser = args.denoise.then(Denoiser::default);
+
+    if denoiser.is_some() {
+        scene.camera.aovs = aovs.iter().chain(DenoiseFeatures::AOVS).collect();
+    }
+
+    if args.adaptive_error.is_some() || args.min_samples.is_some() {
+        let adaptive = scene.camera.render_quality.adaptive.get_or_insert_default();
+
+        if let Some(error_threshold) = args.adaptive_error {
+            adaptive.error_threshold = error_threshold;
+        }
+
+        if let Some(min_samples) = args.min_samples {
+            adaptive.min_samples_per_pixel = min_samples;
+        }
+    }
+
+    if let Some(operator) = args.tone_map {
+        scene.camera.tone_mapping = ToneMapping::new(operator);
+    }
+
+    if let Some(exposure) = args.exposure {
+        scene.camera.tone_mapping.exposure = exposure;
+    }
+
+    if let Some(white_point) = args.white_point {
+        scene.camera.tone_mapping.white_point = white_point;
+    }
+
+    if let Some(size) = args.tile_size {
+        scene.camera.tiles.size = size;
+    }
+
+    if let Some(order) = args.tile_order {
+        scene.camera.tiles.order = order;
+    }
+
+    let camera = scene.camera();
+
+    let region = args.crop.unwrap_or_else(|| PixelRegion::full(camera.dimensions()));
+
+    if !region.fits_within(camera.dimensions()) {
+        return Err(format!(
+            "crop region doesn't fit within the {}x{} image",
+            camera.dimensions().width,
+            camera.dimensions().height,
+        ));
+    }
+
+    let output = args.output.unwrap_or_else(|| {
+        let extension = args.format.map_or("png", |format| format.extension());
+        PathBuf::from(format!("{name}.{extension}"))
+    });
+
+    let format = match args.format {
+        Some(format) => format,
+        None => OutputFormat::from_path(&output)
+            .ok_or_else(|| format!("can't infer an image format from '{}', use --format", output.display()))?,
+    };
+
+    let debug_frequency = if args.quiet { 0 } else { 20 };
+    let timer_name = (!args.quiet).then_some("render");
+
+    let integrator = (args.integrator)();
+    let tone_mapping = scene.camera.tone_mapping;
+    let rendered_aovs = scene.camera.aovs;
+    let control = RenderControl::with_budget(args.budget);
+
+    let outcome = match args.progressive {
+        Some(samples_per_pass) => {
+            let _timer = timer_name.map(|name| ScopedTimer::new(name, false));
+
+            let mut last_snapshot: Option<Duration> = None;
+            let mut snapshot_error = None;
+
+            let outcome = camera.render_progressive(&scene.world, &scene.lights, integrator.as_ref(), region, samples_per_pass, &control, |info, accumulator| {
+                if !args.quiet {
+                    println!("Pass {}: {}/{} samples", info.pass, info.samples_per_pixel, info.target_samples_per_pixel);
+                }
+
+                // the final pass is saved below like any other render
+                let is_final = info.samples_per_pixel >= info.target_samples_per_pixel
+                    || control.budget.noise_threshold.is_some_and(|threshold| accumulator.noise() <= threshold);
+                let is_due = args.snapshot_interval.is_none_or(|interval| last_snapshot.is_none_or(|last| info.elapsed - last >= interval));
+
+                if is_due && !is_final {
+                    last_snapshot = Some(info.elapsed);
+
+                    let snapshot = finish_image(accumulator.snapshot(), accumulator.aovs(rendered_aovs), aovs, denoiser);
+
+                    if let Err(err) = save_render(&snapshot.0, &snapshot.1, &output, format, &tone_mapping) {
+                        snapshot_error = Some(err);
+                        return ControlFlow::Break(());
+                    }
+                }
+
+                ControlFlow::Continue(())
+            });
+
+            if let Some(err) = snapshot_error {
+                return Err(err);
+            }
+
+            outcome
+        }
+        None => camera.render_region_par(&scene.world, &scene.lights, integrator.as_ref(), region, &control, debug_frequency, timer_name),
+    };
+
+    if !args.quiet && outcome.stop_reason != StopReason::Completed {
+        println!(
+            "Stopped early, {}: {} samples in {:.3}s",
+            outcome.stop_reason,
+            outcome.total_samples,
+            outcome.elapsed.as_secs_f32(),
+        );
+    }
+
+    if let Some(path) = &args.sample_map {
+        output::sample_count_image(&outcome.sample_counts)
+            .save(path)
+            .map_err(|err| format!("failed to save '{}': {err}", path.display()))?;
+    }
+
+    let (image, aovs) = finish_image(outcome.image, outcome.aovs, aovs, denoiser);
+
+    save_render(&image, &aovs, &output, format, &tone_mapping)
+}
+
+// denoises the image if asked to, and drops the AOVs that were only rendered for the denoiser
+fn finish_image(image: Rgb32FImage, rendered: Vec<(Aov, Rgb32FImage)>, wanted: AovSet, denoiser: Option<Denoiser>) -> (Rgb32FImage, Vec<(Aov, Rgb32FImage)>) {
+    let image = match denoiser {
+        Some(denoiser) => denoiser.denoise(&image, DenoiseFeatures::from_aovs(&rendered).expect("Denoise features should have been rendered")),
+        None => image,
+    };
+
+    (image, rendered.into_iter().filter(|(aov, _)| wanted.contains(*aov)).collect())
+}
+
+// AOVs are added to OpenEXR output as layers, other formats get a file per AOV next to the output
+fn save_render(image: &Rgb32FImage, aovs: &[(Aov, Rgb32FImage)], output: &Path, format: OutputFormat, tone_mapping: &ToneMapping) -> Result<(), String> {
+    if format == OutputFormat::OpenExr && !aovs.is_empty() {
+        return output::save_exr_layers(image, aovs, output).map_err(|err| save_error(output, err));
+    }
+
+    output::save(image, output, format, tone_mapping).map_err(|err| save_error(output, err))?;
+
+    for (aov, aov_image) in aovs {
+        let path = output.with_extension(format!("{}.{}", aov.name(), format.extension()));
+        output::save_aov(aov_image, *aov, &path, format, tone_mapping).map_err(|err| save_error(&path, err))?;
+    }
+
+    Ok(())
+}
+
+fn save_error(path: &Path, err: ImageError) -> String {
+    format!("failed to save '{}': {err}", path.display())
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut scene = None;
+
+    let mut parsed = Args {
+        scene: String::new(),
+        output: None,
+        format: None,
+        resolution: None,
+        width: None,
+        samples: None,
+        depth: None,
+        min_depth: None,
+        adaptive_error: None,
+        min_samples: None,
+        sampler: None,
+        filter: None,
+        filter_radius: None,
+        filter_mode: None,
+        aovs: None,
+        denoise: false,
+        sample_map: None,
+        integrator: || Box::new(PathIntegrator),
+        tone_map: None,
+        exposure: None,
+        white_point: None,
+        progressive: None,
+        snapshot_interval: None,
+        budget: RenderBudget::default(),
+        threads: None,
+        seed: None,
+        crop: None,
+        tile_size: None,
+        tile_order: None,
+        quiet: false,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("'{arg}' requires a value"));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-l" | "--list" => return Ok(Command::List),
+            "-q" | "--quiet" => parsed.quiet = true,
+            "-o" | "--output" => parsed.output = Some(PathBuf::from(value()?)),
+            "--format" => {
+                let format = value()?;
+
+                parsed.format = Some(
+                    OutputFormat::from_extension(&format)
+                        .ok_or_else(|| format!("unknown image format '{format}'"))?
+                );
+            }
+            "-r" | "--resolution" => {
+                let resolution = value()?;
+
+                parsed.resolution = Some(
+                    resolution.split_once('x')
+                        .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+                        .ok_or_else(|| format!("invalid resolution '{resolution}', expected e.g. 1920x1080"))?
+                );
+            }
+            "-w" | "--width" => parsed.width = Some(parse_number(&arg, &value()?)?),
+            "-s" | "--samples" => parsed.samples = Some(parse_number(&arg, &value()?)?),
+            "-d" | "--depth" => parsed.depth = Some(parse_number(&arg, &value()?)?),
+            "--min-depth" => parsed.min_depth = Some(parse_number(&arg, &value()?)?),
+            "--adaptive" => parsed.adaptive_error = Some(parse_positive(&arg, &value()?)?),
+            "--min-samples" => parsed.min_samples = Some(parse_number(&arg, &value()?)?),
+            "--sampler" => {
+                let name = value()?;
+                parsed.sampler = Some(SamplerKind::from_name(&name).ok_or_else(|| format!("unknown sampler '{name}'"))?);
+            }
+            "--filter" => {
+                let name = value()?;
+                parsed.filter = Some(FilterKind::from_name(&name).ok_or_else(|| format!("unknown filter '{name}'"))?);
+            }
+            "--filter-radius" => parsed.filter_radius = Some(parse_positive(&arg, &value()?)?),
+            "--filter-mode" => {
+                let name = value()?;
+                parsed.filter_mode = Some(FilterMode::from_name(&name).ok_or_else(|| format!("unknown filter mode '{name}'"))?);
+            }
+            "--aov" => {
+                let list = value()?;
+
+                parsed.aovs = Some(match list.as_str() {
+                    "all" => AovSet::ALL,
+                    _ => list.split(',')
+                        .map(|name| Aov::from_name(name).ok_or_else(|| format!("unknown AOV '{name}'")))
+                        .collect::<Result<_, _>>()?,
+                });
+            }
+            "--denoise" => parsed.denoise = true,
+            "--sample-map" => parsed.sample_map = Some(PathBuf::from(value()?)),
+            "-i" | "--integrator" => {
+                let name = value()?;
+                parsed.integrator = integrator::find(&name).ok_or_else(|| format!("unknown integrator '{name}'"))?;
+            }
+            "--tonemap" => {
+                let name = value()?;
+                parsed.tone_map = Some(ToneMapOperator::from_name(&name).ok_or_else(|| format!("unknown tone mapping operator '{name}'"))?);
+            }
+            "--exposure" => parsed.exposure = Some(parse_finite(&arg, &value()?)?),
+            "--white" => parsed.white_point = Some(parse_positive(&arg, &value()?)?),
+            "--progressive" => parsed.progressive = Some(parse_number(&arg, &value()?)?),
+            "--snapshot-interval" => parsed.snapshot_interval = Some(parse_seconds(&arg, &value()?)?),
+            "--time-limit" => parsed.budget.time_limit = Some(parse_seconds(&arg, &value()?)?),
+            "--sample-limit" => parsed.budget.sample_limit = Some(parse_number(&arg, &value()?)?),
+            "--noise" => parsed.budget.noise_threshold = Some(parse_positive(&arg, &value()?)?),
+            "-j" | "--threads" => parsed.threads = Some(parse_number(&arg, &value()?)?),
+            "--seed" => parsed.seed = Some(parse_number(&arg, &value()?)?),
+            "--tile-size" => parsed.tile_size = Some(parse_number(&arg, &value()?)?),
+            "--tile-order" => {
+                let name = value()?;
+                parsed.tile_order = Some(TileOrder::from_name(&name).ok_or_else(|| format!("unknown tile order '{name}'"))?);
+            }
+            "--crop" => {
+                let crop = value()?;
+                let parts = crop.split(',').map(str::parse::<u32>).collect::<Result<Vec<_>, _>>();
+
+                parsed.crop = match parts.as_deref() {
+                    Ok(&[x, y, w, h]) => NonZeroU32::new(w).zip(NonZeroU32::new(h))
+                        .map(|(width, height)| PixelRegion { x, y, width, height }),
+                    _ => None,
+                };
+
+                if parsed.crop.is_none() {
+                    return Err(format!("invalid crop region '{crop}', expected X,Y,W,H with a nonzero size"));
+                }
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ if scene.is_none() => scene = Some(arg),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    parsed.scene = scene.ok_or("no scene given")?;
+
+    if parsed.budget.noise_threshold.is_some() && parsed.progressive.is_none() {
+        return Err("'--noise' requires '--progressive'".to_string());
+    }
+
+    Ok(Command::Render(Box::new(parsed)))
+}
+
+fn parse_seconds(option: &str, value: &str) -> Result<Duration, String> {
+    value.parse().ok()
+        .and_then(|seconds| Duration::try_from_secs_f32(seconds).ok())
+        .ok_or_else(|| format!("invalid value '{value}' for '{option}'"))
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value '{value}' for '{option}'"))
+}
+
+fn parse_finite(option: &str, value: &str) -> Result<f32, String> {
+    parse_number(option, value)
+        .and_then(|n: f32| if n.is_finite() { Ok(n) } else { Err(format!("invalid value '{value}' for '{option}'")) })
+}
+
+fn parse_positive(option: &str, value: &str) -> Result<f32, String> {
+    parse_finite(option, value)
+        .and_then(|n| if n > 0.0 { Ok(n) } else { Err(format!("invalid value '{value}' for '{option}', expected a positive number")) })
+}
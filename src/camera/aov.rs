@@ -0,0 +1,211 @@
+use glm::{Vec2, Vec3};
+use crate::color::Color;
+use crate::integrator::PathSample;
+
+// arbitrary output variables, render passes besides the image itself for compositing and denoising
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Aov {
+    // distance along the camera ray to the first hit, infinite where it escaped
+    Depth,
+    // world space shading normal of the first hit
+    Normal,
+    Albedo,
+    Uv,
+    // see HittableList for how objects are numbered, 0 where nothing was hit
+    ObjectId,
+    // see MaterialIds, 0 where nothing was hit
+    MaterialId,
+    // light emitted by the first hit
+    Emission,
+    // light that reached the camera after at most one bounce, including emission seen directly
+    Direct,
+    Indirect,
+    SampleCount,
+}
+
+impl Aov {
+    pub const ALL: [Self; 10] = [
+        Self::Depth,
+        Self::Normal,
+        Self::Albedo,
+        Self::Uv,
+        Self::ObjectId,
+        Self::MaterialId,
+        Self::Emission,
+        Self::Direct,
+        Self::Indirect,
+        Self::SampleCount,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|aov| aov.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Depth => "depth",
+            Self::Normal => "normal",
+            Self::Albedo => "albedo",
+            Self::Uv => "uv",
+            Self::ObjectId => "object_id",
+            Self::MaterialId => "material_id",
+            Self::Emission => "emission",
+            Self::Direct => "direct",
+            Self::Indirect => "indirect",
+            Self::SampleCount => "sample_count",
+        }
+    }
+
+    // names of the components that are used in the AOV's image, the rest are zero
+    pub fn channels(self) -> &'static [&'static str] {
+        match self {
+            Self::Depth => &["Z"],
+            Self::Normal => &["X", "Y", "Z"],
+            Self::Uv => &["U", "V"],
+            Self::ObjectId | Self::MaterialId | Self::SampleCount => &["Y"],
+            Self::Albedo | Self::Emission | Self::Direct | Self::Indirect => &["R", "G", "B"],
+        }
+    }
+
+    // the sample count is kept for every render, the others need their own buffer
+    pub(super) fn needs_samples(self) -> bool {
+        self != Self::SampleCount
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AovSet(u16);
+
+impl AovSet {
+    pub const ALL: Self = Self((1 << Aov::ALL.len()) - 1);
+
+    pub fn insert(&mut self, aov: Aov) {
+        self.0 |= Self::bit(aov);
+    }
+
+    pub fn contains(self, aov: Aov) -> bool {
+        self.0 & Self::bit(aov) != 0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = Aov> {
+        Aov::ALL.into_iter().filter(move |&aov| self.contains(aov))
+    }
+
+    fn bit(aov: Aov) -> u16 {
+        1 << aov as u16
+    }
+}
+
+impl FromIterator<Aov> for AovSet {
+    fn from_iter<T: IntoIterator<Item = Aov>>(iter: T) -> Self {
+        let mut set = Self::default();
+        iter.into_iter().for_each(|aov| set.insert(aov));
+        set
+    }
+}
+
+// running sums of a pixel's AOVs; samples count equally, whatever the pixel filter.
+// surface properties are averaged over the samples that hit something, while ids are those of the pixel's first hit
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AovPixel {
+    pub samples: u32,
+    pub hits: u32,
+    pub depth_sum: f32,
+    pub normal_sum: Vec3,
+    pub albedo_sum: Color,
+    pub uv_sum: Vec2,
+    pub emission_sum: Color,
+    pub direct_sum: Color,
+    pub indirect_sum: Color,
+    pub object_id: u32,
+    pub material_id: u32,
+}
+
+impl AovPixel {
+    pub fn add_sample(&mut self, sample: &PathSample) {
+        if let Some(hit) = sample.first_hit {
+            if self.hits == 0 {
+                self.object_id = hit.object_id;
+                self.material_id = hit.material_id;
+            }
+
+            self.hits += 1;
+            self.depth_sum += hit.depth;
+            self.normal_sum += hit.normal;
+            self.albedo_sum += hit.albedo;
+            self.uv_sum += hit.uv;
+            self.emission_sum += hit.emission;
+        }
+
+        self.direct_sum += sample.direct;
+        self.indirect_sum += sample.radiance - sample.direct;
+        self.samples += 1;
+    }
+
+    // the AOV's value as a color, with unused channels left at zero; None for the sample count, which isn't kept here
+    pub fn value(&self, aov: Aov) -> Option<Color> {
+        let hit_mean = |sum: Color| if self.hits == 0 { Color::zeros() } else { sum / self.hits as f32 };
+        let sample_mean = |sum: Color| if self.samples == 0 { Color::zeros() } else { sum / self.samples as f32 };
+
+        let value = match aov {
+            Aov::Depth if self.hits == 0 => Color::new(f32::INFINITY, 0.0, 0.0),
+            Aov::Depth => Color::new(self.depth_sum / self.hits as f32, 0.0, 0.0),
+            Aov::Normal => self.normal_sum.try_normalize(f32::EPSILON).unwrap_or_default(),
+            Aov::Albedo => hit_mean(self.albedo_sum),
+            Aov::Uv => hit_mean(Color::new(self.uv_sum.x, self.uv_sum.y, 0.0)),
+            Aov::ObjectId => Color::new(self.object_id as f32, 0.0, 0.0),
+            Aov::MaterialId => Color::new(self.material_id as f32, 0.0, 0.0),
+            Aov::Emission => sample_mean(self.emission_sum),
+            Aov::Direct => sample_mean(self.direct_sum),
+            Aov::Indirect => sample_mean(self.indirect_sum),
+            Aov::SampleCount => return None,
+        };
+
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::integrator::{FirstHit, Termination};
+    use super::*;
+
+    fn sample(first_hit: Option<FirstHit>) -> PathSample {
+        PathSample { radiance: Color::zeros(), direct: Color::zeros(), bounces: 0, termination: Termination::Escaped, first_hit }
+    }
+
+    fn hit(object_id: u32, material_id: u32) -> Option<FirstHit> {
+        Some(FirstHit {
+            depth: 2.0,
+            normal: Vec3::z(),
+            albedo: Color::repeat(0.5),
+            uv: Vec2::zeros(),
+            emission: Color::zeros(),
+            object_id,
+            material_id,
+        })
+    }
+
+    #[test]
+    fn ids_come_from_the_first_hit_even_after_a_miss() {
+        let mut pixel = AovPixel::default();
+        [sample(None), sample(hit(3, 7)), sample(hit(4, 8))].iter().for_each(|sample| pixel.add_sample(sample));
+
+        assert_eq!(pixel.value(Aov::ObjectId), Some(Color::new(3.0, 0.0, 0.0)));
+        assert_eq!(pixel.value(Aov::MaterialId), Some(Color::new(7.0, 0.0, 0.0)));
+        assert_eq!(pixel.value(Aov::Depth), Some(Color::new(2.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn pixels_without_hits_have_no_ids() {
+        let mut pixel = AovPixel::default();
+        pixel.add_sample(&sample(None));
+
+        assert_eq!(pixel.value(Aov::ObjectId), Some(Color::zeros()));
+        assert_eq!(pixel.value(Aov::Depth), Some(Color::new(f32::INFINITY, 0.0, 0.0)));
+    }
+}
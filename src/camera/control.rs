@@ -0,0 +1,110 @@
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use image::Rgb32FImage;
+use crate::camera::aov::Aov;
+use crate::camera::progressive::SampleCountMap;
+use crate::camera::tiles::TileProgress;
+
+// cheap to clone, every clone cancels the same render
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct RenderBudget {
+    pub time_limit: Option<Duration>,
+    // summed over every pixel of the rendered region
+    pub sample_limit: Option<u64>,
+    // mean relative standard error of the pixels, only checked between passes of a progressive render
+    pub noise_threshold: Option<f32>,
+}
+
+impl RenderBudget {
+    // the most samples per pixel a pass can take without going over the sample limit
+    pub fn pass_samples(&self, samples: u32, pixel_count: u64, total_samples: u64) -> u32 {
+        match self.sample_limit {
+            Some(limit) => {
+                let affordable = limit.saturating_sub(total_samples) / pixel_count.max(1);
+                samples.min(u32::try_from(affordable).unwrap_or(u32::MAX))
+            }
+            None => samples,
+        }
+    }
+}
+
+// called from the render threads as each tile finishes
+pub type TileCallback = Arc<dyn Fn(&TileProgress) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub struct RenderControl {
+    pub cancellation: CancellationToken,
+    pub budget: RenderBudget,
+    pub on_tile: Option<TileCallback>,
+}
+
+impl RenderControl {
+    pub fn with_budget(budget: RenderBudget) -> Self {
+        Self { budget, ..Self::default() }
+    }
+
+    pub fn with_tile_callback(mut self, on_tile: impl Fn(&TileProgress) + Send + Sync + 'static) -> Self {
+        self.on_tile = Some(Arc::new(on_tile));
+        self
+    }
+
+    // reasons that can stop a render in the middle of a pass
+    pub fn interruption(&self, start: Instant) -> Option<StopReason> {
+        if self.cancellation.is_cancelled() {
+            Some(StopReason::Cancelled)
+        } else if self.budget.time_limit.is_some_and(|limit| start.elapsed() >= limit) {
+            Some(StopReason::TimeLimit)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    // every pixel got the render quality's samples per pixel
+    Completed,
+    // by the cancellation token, or by a progressive render's pass callback
+    Cancelled,
+    TimeLimit,
+    SampleLimit,
+    NoiseThreshold,
+}
+
+impl Display for StopReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Completed => write!(f, "completed"),
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::TimeLimit => write!(f, "time limit reached"),
+            Self::SampleLimit => write!(f, "sample limit reached"),
+            Self::NoiseThreshold => write!(f, "noise threshold reached"),
+        }
+    }
+}
+
+pub struct RenderOutcome {
+    // pixels a render was stopped before reaching keep whatever they had accumulated, which may be nothing
+    pub image: Rgb32FImage,
+    pub sample_counts: SampleCountMap,
+    // the camera's AOVs, in the order of Aov::ALL
+    pub aovs: Vec<(Aov, Rgb32FImage)>,
+    pub stop_reason: StopReason,
+    pub total_samples: u64,
+    pub elapsed: Duration,
+}
@@ -1,5 +1,6 @@
 use std::num::{NonZero, NonZeroU32};
 
+#[derive(Copy, Clone, Debug)]
 pub struct CameraDimensions {
     pub width: NonZeroU32,
     pub height: NonZeroU32,
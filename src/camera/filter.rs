@@ -0,0 +1,324 @@
+use std::f32::consts::PI;
+use glm::Vec2;
+use crate::camera::region::PixelRegion;
+use crate::color::Color;
+
+// how samples around a pixel are weighted into it; all filters are separable, the product of the same 1D profile along x & y
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FilterKind {
+    #[default]
+    Box,
+    Tent,
+    Gaussian,
+    // Mitchell-Netravali with B = C = 1/3
+    Mitchell,
+    // windowed sinc, with radius lobes
+    Lanczos,
+}
+
+impl FilterKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "box" => Some(Self::Box),
+            "tent" | "triangle" => Some(Self::Tent),
+            "gaussian" => Some(Self::Gaussian),
+            "mitchell" => Some(Self::Mitchell),
+            "lanczos" => Some(Self::Lanczos),
+            _ => None,
+        }
+    }
+
+    pub fn default_radius(self) -> f32 {
+        match self {
+            Self::Box => 0.5,
+            Self::Tent => 1.0,
+            Self::Gaussian => 1.5,
+            Self::Mitchell | Self::Lanczos => 2.0,
+        }
+    }
+
+    // zero at and beyond the radius
+    fn evaluate_1d(self, x: f32, radius: f32) -> f32 {
+        let x = x.abs();
+
+        if x >= radius {
+            return 0.0;
+        }
+
+        match self {
+            Self::Box => 1.0,
+            Self::Tent => 1.0 - x / radius,
+            Self::Gaussian => {
+                // shifted down so it reaches zero at the radius instead of being cut off
+                let sigma = radius / 3.0;
+                let gaussian = |x: f32| (-x * x / (2.0 * sigma * sigma)).exp();
+
+                gaussian(x) - gaussian(radius)
+            }
+            Self::Mitchell => mitchell(2.0 * x / radius),
+            Self::Lanczos => sinc(x) * sinc(x / radius),
+        }
+    }
+}
+
+fn mitchell(x: f32) -> f32 {
+    const B: f32 = 1.0 / 3.0;
+    const C: f32 = 1.0 / 3.0;
+
+    let value = if x < 1.0 {
+        (12.0 - 9.0 * B - 6.0 * C) * x.powi(3) + (-18.0 + 12.0 * B + 6.0 * C) * x.powi(2) + (6.0 - 2.0 * B)
+    } else {
+        (-B - 6.0 * C) * x.powi(3) + (6.0 * B + 30.0 * C) * x.powi(2) + (-12.0 * B - 48.0 * C) * x + (8.0 * B + 24.0 * C)
+    };
+
+    value / 6.0
+}
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-5 { 1.0 } else { (PI * x).sin() / (PI * x) }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FilterMode {
+    // sample positions are distributed like the filter, and every sample only counts towards its own pixel
+    #[default]
+    ImportanceSampled,
+    // sample positions are uniform over their pixel, and every sample is added to each pixel within the filter's
+    // radius, weighted by the filter; the image is each pixel's weighted sum over its total weight
+    Splatted,
+}
+
+impl FilterMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "importance" => Some(Self::ImportanceSampled),
+            "splat" => Some(Self::Splatted),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PixelFilter {
+    pub kind: FilterKind,
+    // in pixels, from the pixel's center
+    pub radius: f32,
+    pub mode: FilterMode,
+}
+
+impl PixelFilter {
+    pub fn new(kind: FilterKind) -> Self {
+        Self { kind, radius: kind.default_radius(), mode: FilterMode::default() }
+    }
+
+    pub fn evaluate(&self, offset: Vec2) -> f32 {
+        self.kind.evaluate_1d(offset.x, self.radius) * self.kind.evaluate_1d(offset.y, self.radius)
+    }
+}
+
+// a box filter half a pixel wide is plain averaging of samples spread over the pixel
+impl Default for PixelFilter {
+    fn default() -> Self {
+        Self::new(FilterKind::Box)
+    }
+}
+
+// draws offsets from a tabulated distribution of the filter's absolute value along each axis; the returned weight is
+// the filter over that pdf, normalized by the filter's integral, so negative lobes come out as negative weights
+pub(super) struct FilterSampler {
+    filter: PixelFilter,
+    cdf: Vec<f32>,
+    pdf: Vec<f32>,
+    integral: f32,
+}
+
+impl FilterSampler {
+    const BINS: usize = 64;
+    const SUBSAMPLES: usize = 16;
+
+    pub fn new(filter: PixelFilter) -> Self {
+        let bin_width = 2.0 * filter.radius / Self::BINS as f32;
+
+        let mut integral = 0.0;
+
+        let bins: Vec<f32> = (0..Self::BINS)
+            .map(|bin| {
+                let (abs_sum, sum) = (0..Self::SUBSAMPLES)
+                    .map(|i| {
+                        let x = -filter.radius + (bin as f32 + (i as f32 + 0.5) / Self::SUBSAMPLES as f32) * bin_width;
+                        filter.kind.evaluate_1d(x, filter.radius)
+                    })
+                    .fold((0.0, 0.0), |(abs_sum, sum), value| (abs_sum + value.abs(), sum + value));
+
+                integral += sum * bin_width / Self::SUBSAMPLES as f32;
+                abs_sum / Self::SUBSAMPLES as f32
+            })
+            .collect();
+
+        let total = bins.iter().sum::<f32>() * bin_width;
+
+        let pdf = bins.iter().map(|value| value / total).collect();
+
+        let cdf = std::iter::once(0.0)
+            .chain(bins.iter().scan(0.0, |acc, value| {
+                *acc += value * bin_width / total;
+                Some(*acc)
+            }))
+            .collect();
+
+        Self { filter, cdf, pdf, integral }
+    }
+
+    pub fn sample(&self, u: Vec2) -> (Vec2, f32) {
+        let (x, weight_x) = self.sample_1d(u.x);
+        let (y, weight_y) = self.sample_1d(u.y);
+
+        (Vec2::new(x, y), weight_x * weight_y)
+    }
+
+    fn sample_1d(&self, u: f32) -> (f32, f32) {
+        let bin = (self.cdf.partition_point(|&cdf| cdf <= u) - 1).min(Self::BINS - 1);
+        let bin_width = 2.0 * self.filter.radius / Self::BINS as f32;
+
+        let bin_probability = self.cdf[bin + 1] - self.cdf[bin];
+        let t = if bin_probability > 0.0 { ((u - self.cdf[bin]) / bin_probability).min(1.0) } else { 0.5 };
+
+        let x = -self.filter.radius + (bin as f32 + t) * bin_width;
+        let weight = self.filter.kind.evaluate_1d(x, self.filter.radius) / (self.pdf[bin] * self.integral);
+
+        (x, weight)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub(super) struct SplatPixel {
+    pub weighted_sum: Color,
+    pub weight: f32,
+}
+
+// the splats of one tile's samples, which reach up to the filter's radius past the tile; kept separately so they can
+// be added to the image in a fixed order, however the tiles were scheduled
+pub(super) struct SplatBuffer {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    pixels: Vec<SplatPixel>,
+}
+
+impl SplatBuffer {
+    pub fn around(tile: PixelRegion, bounds: PixelRegion, radius: f32) -> Self {
+        let margin = (radius + 0.5).ceil() as u32;
+
+        let x = tile.x.saturating_sub(margin).max(bounds.x);
+        let y = tile.y.saturating_sub(margin).max(bounds.y);
+        let right = (tile.x + tile.width.get() + margin).min(bounds.x + bounds.width.get());
+        let bottom = (tile.y + tile.height.get() + margin).min(bounds.y + bounds.height.get());
+
+        let (width, height) = (right - x, bottom - y);
+
+        Self { x, y, width, height, pixels: vec![SplatPixel::default(); (width * height) as usize] }
+    }
+
+    // position is in continuous image coordinates, where the center of pixel (x, y) is at (x + 0.5, y + 0.5)
+    pub fn splat(&mut self, filter: &PixelFilter, position: Vec2, radiance: Color) {
+        let first_x = (position.x - 0.5 - filter.radius).ceil().max(self.x as f32) as u32;
+        let first_y = (position.y - 0.5 - filter.radius).ceil().max(self.y as f32) as u32;
+        let last_x = (position.x - 0.5 + filter.radius).floor().min((self.x + self.width) as f32 - 1.0);
+        let last_y = (position.y - 0.5 + filter.radius).floor().min((self.y + self.height) as f32 - 1.0);
+
+        if last_x < first_x as f32 || last_y < first_y as f32 {
+            return;
+        }
+
+        for y in first_y..=last_y as u32 {
+            for x in first_x..=last_x as u32 {
+                let weight = filter.evaluate(Vec2::new(x as f32 + 0.5, y as f32 + 0.5) - position);
+
+                if weight != 0.0 {
+                    let pixel = &mut self.pixels[((y - self.y) * self.width + x - self.x) as usize];
+                    pixel.weighted_sum += weight * radiance;
+                    pixel.weight += weight;
+                }
+            }
+        }
+    }
+
+    // absolute coordinates of every pixel, with its splats
+    pub fn pixels(&self) -> impl Iterator<Item = (u32, u32, &SplatPixel)> {
+        self.pixels.iter().enumerate().map(|(i, pixel)| {
+            (self.x + i as u32 % self.width, self.y + i as u32 / self.width, pixel)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+    use super::*;
+
+    const KINDS: [FilterKind; 5] = [FilterKind::Box, FilterKind::Tent, FilterKind::Gaussian, FilterKind::Mitchell, FilterKind::Lanczos];
+
+    // midpoints of an n by n grid over [0, 1)²
+    fn grid(n: u32) -> impl Iterator<Item = Vec2> {
+        (0..n * n).map(move |i| Vec2::new((i % n) as f32 + 0.5, (i / n) as f32 + 0.5) / n as f32)
+    }
+
+    // the 2D weight is the product of two independent 1D ones, so checking one axis covers both
+    #[test]
+    fn importance_sampled_weights_average_to_one() {
+        for kind in KINDS {
+            let sampler = FilterSampler::new(PixelFilter::new(kind));
+
+            let samples = 4096;
+            let mean = (0..samples).map(|i| sampler.sample_1d((i as f32 + 0.5) / samples as f32).1).sum::<f32>() / samples as f32;
+
+            assert!((mean - 1.0).abs() < 1e-3, "{kind:?} weights average to {mean}");
+        }
+    }
+
+    #[test]
+    fn importance_sampled_offsets_stay_within_the_radius() {
+        for kind in KINDS {
+            let filter = PixelFilter::new(kind);
+            let sampler = FilterSampler::new(filter);
+
+            for u in grid(64).chain([Vec2::zeros(), Vec2::repeat(1.0 - f32::EPSILON)]) {
+                let (offset, weight) = sampler.sample(u);
+
+                assert!(offset.x.abs() <= filter.radius && offset.y.abs() <= filter.radius, "{kind:?} offset {offset}");
+                assert!(weight.is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn splatted_weights_average_to_the_filter_integral() {
+        let size = NonZeroU32::new(16).expect("16 is non-zero");
+        let region = PixelRegion { x: 0, y: 0, width: size, height: size };
+        let samples_per_axis = 8;
+
+        for kind in KINDS {
+            let filter = PixelFilter::new(kind);
+            let integral = FilterSampler::new(filter).integral;
+
+            let mut buffer = SplatBuffer::around(region, region, filter.radius);
+            for y in 0..size.get() {
+                for x in 0..size.get() {
+                    for u in grid(samples_per_axis) {
+                        buffer.splat(&filter, Vec2::new(x as f32, y as f32) + u, Color::new(0.25, 0.5, 1.0));
+                    }
+                }
+            }
+
+            // away from the edges, every pixel is covered by the whole filter
+            for (x, y, pixel) in buffer.pixels().filter(|&(x, y, _)| (4..12).contains(&x) && (4..12).contains(&y)) {
+                let mean = pixel.weight / (samples_per_axis * samples_per_axis) as f32 / (integral * integral);
+                assert!((mean - 1.0).abs() < 1e-2, "{kind:?} weights of ({x}, {y}) average to {mean}");
+
+                let color = pixel.weighted_sum / pixel.weight;
+                assert!((color - Color::new(0.25, 0.5, 1.0)).abs().max() < 1e-4, "{kind:?} ({x}, {y}) is {color}");
+            }
+        }
+    }
+}
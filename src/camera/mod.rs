@@ -1,47 +1,63 @@
 pub mod dimensions;
 pub mod render_quality;
 pub mod focus_settings;
-
-use std::ops::RangeInclusive;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicU32, Ordering};
-use glm::Vec3;
-use image::{ImageBuffer, Rgb, RgbImage};
-use crate::color::{Color, transform_color_to_pixel};
-use rayon::iter::ParallelIterator;
-use rayon::prelude::ParallelBridge;
+pub mod settings;
+pub mod region;
+pub mod progressive;
+pub mod control;
+pub mod tiles;
+pub mod filter;
+pub mod aov;
+pub mod shutter;
+pub mod projection;
+pub mod model;
+
+use std::num::NonZeroU32;
+use std::ops::{ControlFlow, Range};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Instant;
+use glm::{Vec2, Vec3};
+use image::Rgb32FImage;
+use crate::color::Color;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use crate::camera::dimensions::CameraDimensions;
 use crate::camera::focus_settings::FocusSettings;
+use crate::camera::aov::{Aov, AovPixel, AovSet};
+use crate::camera::control::{RenderControl, RenderOutcome, StopReason};
+use crate::camera::filter::{FilterMode, FilterSampler, PixelFilter, SplatBuffer};
+use crate::camera::progressive::{AccumulatedPixel, Accumulator, PassInfo};
+use crate::camera::model::{CameraFrame, CameraModel};
+use crate::camera::projection::Projection;
+use crate::camera::region::PixelRegion;
 use crate::camera::render_quality::{InternalRenderQuality, RenderQuality};
+use crate::camera::shutter::Shutter;
+use crate::camera::tiles::{TileProgress, TileSettings};
 use crate::hittable::Hittable;
-use crate::interval::Interval;
-use crate::material::ScatterResult;
+use crate::hittable::hittable_list::HittableList;
+use crate::integrator::{Integrator, PathSample, SceneContext, Termination};
+use crate::material::MaterialIds;
 use crate::ray::Ray;
+use crate::sampler::{SampleStream, Sampler};
 use crate::util::timer::ScopedTimer;
-use crate::util::vec3_random::random_vec_in_unit_disk;
 
 pub struct Camera {
-    pub center: Vec3,
-
     dimensions: CameraDimensions,
     render_quality: InternalRenderQuality,
     background_color: Color,
-
-    defocus_angle: f32,
-    defocus_disk_u: Vec3,
-    defocus_disk_v: Vec3,
-
-    pixel00_loc: Vec3,
-    pixel_delta_u: Vec3,
-    pixel_delta_v: Vec3,
+    tiles: TileSettings,
+    sampler: Box<dyn Sampler>,
+    filter: PixelFilter,
+    filter_sampler: FilterSampler,
+    aovs: AovSet,
+    model: Box<dyn CameraModel>,
+    shutter: Shutter,
 }
 
 impl Camera {
-    const UP_VECTOR: Vec3 = Vec3::new(0.0, 1.0, 0.0);
-
-    const MIN_RAY_DISTANCE: f32 = 0.001;
-    const RANDOM_RAY_OFFSET_RANGE: RangeInclusive<f32> = -0.5..=0.5f32;
+    const ADAPTIVE_BATCH_SAMPLES: u32 = 4;
 
+    #[expect(clippy::too_many_arguments, reason = "built from CameraSettings")]
     pub fn new(
         center: Vec3,
         look_at: Vec3,
@@ -49,56 +65,58 @@ impl Camera {
         dimensions: CameraDimensions,
         render_quality: RenderQuality,
         focus_settings: FocusSettings,
-        fov: f32,
-
+        projection: Projection,
+        tiles: TileSettings,
+        filter: PixelFilter,
+        aovs: AovSet,
+        shutter: Shutter,
     ) -> Self {
-        let h = f32::tan(fov.to_radians() / 2.0);
-        let viewport_height = 2.0 * h * focus_settings.focus_dist;
-        let viewport_width = viewport_height * dimensions.aspect_ratio();
-
-        let w = (center - look_at).normalize();
-        let u = Self::UP_VECTOR.cross(&w).normalize();
-        let v = w.cross(&u);
-
-        let viewport_u = viewport_width * u;
-        let viewport_v = viewport_height * -v;
-
-        let pixel_delta_u = viewport_u / dimensions.width.get() as f32;
-        let pixel_delta_v = viewport_v / dimensions.height.get() as f32;
-
-        let viewport_upper_left = center - focus_settings.focus_dist * w
-            - viewport_u / 2.0 - viewport_v / 2.0;
-
-        let pixel00_loc = viewport_upper_left + 0.5 * (pixel_delta_u + pixel_delta_v);
-
-        let defocus_radius = focus_settings.focus_dist * f32::tan(f32::to_radians(focus_settings.defocus_angle / 2.0));
-        let defocus_disk_u = u * defocus_radius;
-        let defocus_disk_v = v * defocus_radius;
+        let model = projection.build(CameraFrame::look_at(center, look_at), dimensions.aspect_ratio(), focus_settings);
 
+        let sampler = render_quality.sampler.build(render_quality.seed, render_quality.samples_per_pixel);
         let render_quality = render_quality.into();
 
-        let defocus_angle = focus_settings.defocus_angle;
-
         Self {
-            center,
             dimensions,
             render_quality,
             background_color,
-            defocus_angle,
-            defocus_disk_u,
-            defocus_disk_v,
-            pixel00_loc,
-            pixel_delta_u,
-            pixel_delta_v,
+            tiles,
+            sampler,
+            filter,
+            filter_sampler: FilterSampler::new(filter),
+            aovs,
+            model,
+            shutter,
         }
     }
 
-    pub fn render_screen(&self, world: &impl Hittable, debug_frequency: u8) -> RgbImage {
-        let mut img: RgbImage = ImageBuffer::new(self.dimensions.width.get(), self.dimensions.height.get());
+    // replaces the model built from the projection, e.g. with a custom one. it's the model's own frame that places the
+    // camera then, the rest of the settings still apply
+    pub fn with_model(self, model: Box<dyn CameraModel>) -> Self {
+        Self { model, ..self }
+    }
+
+    pub fn dimensions(&self) -> &CameraDimensions {
+        &self.dimensions
+    }
+
+    pub fn render_screen(&self, world: &impl Hittable, lights: &HittableList, integrator: &dyn Integrator, debug_frequency: u8) -> Rgb32FImage {
+        let scene = self.scene_context(world, lights);
+        let region = PixelRegion::full(&self.dimensions);
+
+        let mut accumulator = self.accumulator(region);
+        let mut splats = self.splat_buffer(region, region);
 
         for y in 0..self.dimensions.height.get() {
             for x in 0..self.dimensions.width.get() {
-                img.put_pixel(x, y, self.render_pixel(world, x, y));
+                let samples = self.render_quality.samples_per_pixel();
+                let mut aov = accumulator.aov_pixel(x, y).copied();
+
+                *accumulator.pixel_mut(x, y) = self.sample_pixel_adaptive(&scene, integrator, x, y, samples, splats.as_mut(), aov.as_mut());
+
+                if let (Some(pixel), Some(aov)) = (accumulator.aov_pixel_mut(x, y), aov) {
+                    *pixel = aov;
+                }
             }
 
             if debug_frequency != 0 && (y + 1) % debug_frequency as u32 == 0 {
@@ -106,75 +124,368 @@ impl Camera {
             }
         }
 
-        img
+        if let Some(splats) = splats {
+            accumulator.add_splats(region, &splats);
+        }
+
+        accumulator.snapshot()
+    }
+
+    pub fn render_screen_par(&self, world: &impl Hittable, lights: &HittableList, integrator: &dyn Integrator, debug_frequency: u8, timer_name: Option<&'static str>) -> Rgb32FImage {
+        let region = PixelRegion::full(&self.dimensions);
+
+        self.render_region_par(world, lights, integrator, region, &RenderControl::default(), debug_frequency, timer_name).image
     }
 
-    pub fn render_screen_par(&self, world: &impl Hittable, debug_frequency: u8, timer_name: Option<&'static str>) -> RgbImage {
+    // every pixel is rendered in one go, so a noise threshold can't be checked; use a progressive render for that
+    #[expect(clippy::too_many_arguments, reason = "render entry point")]
+    pub fn render_region_par(
+        &self,
+        world: &impl Hittable,
+        lights: &HittableList,
+        integrator: &dyn Integrator,
+        region: PixelRegion,
+        control: &RenderControl,
+        debug_frequency: u8,
+        timer_name: Option<&'static str>,
+    ) -> RenderOutcome {
+        assert!(region.fits_within(&self.dimensions), "Region must lie within the camera's dimensions");
+
         let _timer = timer_name.map(|name| ScopedTimer::new(name, false));
-        
-        let mut img: RgbImage = ImageBuffer::new(self.dimensions.width.get(), self.dimensions.height.get());
 
-        let lines = Arc::new(AtomicU32::new(0));
+        let start = Instant::now();
+        let scene = self.scene_context(world, lights);
 
-        img.enumerate_pixels_mut().par_bridge().for_each(|(x, y, pixel)| {
-            *pixel = self.render_pixel(world, x, y);
+        let pixel_count = region.width.get() as u64 * region.height.get() as u64;
+        let samples = control.budget.pass_samples(self.render_quality.samples_per_pixel(), pixel_count, 0);
 
-            let curr = lines.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut accumulator = self.accumulator(region);
+        let rendered = AtomicU64::new(0);
 
-            if debug_frequency != 0 && curr % self.dimensions.width == 0 &&  curr / self.dimensions.width % debug_frequency as u32 == 0 {
-                println!("Lines completed: {}/{}", curr / self.dimensions.width, self.dimensions.height);
-            }
-        });
+        if samples > 0 {
+            self.render_tiles(region, &mut accumulator, control, start, 1, debug_frequency, |x, y, pixel, splats, aov| {
+                *pixel = self.sample_pixel_adaptive(&scene, integrator, x, y, samples, splats, aov);
+                rendered.fetch_add(1, Ordering::Relaxed);
+            });
+        }
 
-        img
+        let stop_reason = if rendered.into_inner() < pixel_count {
+            control.interruption(start).unwrap_or(StopReason::SampleLimit)
+        } else if samples < self.render_quality.samples_per_pixel() {
+            StopReason::SampleLimit
+        } else {
+            StopReason::Completed
+        };
+
+        RenderOutcome {
+            image: accumulator.snapshot(),
+            sample_counts: accumulator.sample_counts(),
+            aovs: accumulator.aovs(self.aovs),
+            stop_reason,
+            total_samples: accumulator.pixels.iter().map(|pixel| pixel.samples as u64).sum(),
+            elapsed: start.elapsed(),
+        }
     }
 
-    fn render_pixel(&self, world: &impl Hittable, x: u32, y: u32) -> Rgb<u8> {
-        let mut pixel_color = Color::default();
+    // renders samples_per_pass samples for every pixel at a time until the render quality's samples per pixel are reached,
+    // every pixel has converged when sampling adaptively, or the control stops it; on_pass sees the accumulated result
+    // after each pass and can break to stop early
+    #[expect(clippy::too_many_arguments, reason = "render entry point")]
+    pub fn render_progressive(
+        &self,
+        world: &impl Hittable,
+        lights: &HittableList,
+        integrator: &dyn Integrator,
+        region: PixelRegion,
+        samples_per_pass: NonZeroU32,
+        control: &RenderControl,
+        mut on_pass: impl FnMut(&PassInfo, &Accumulator) -> ControlFlow<()>,
+    ) -> RenderOutcome {
+        assert!(region.fits_within(&self.dimensions), "Region must lie within the camera's dimensions");
+
+        let start = Instant::now();
+        let scene = self.scene_context(world, lights);
+        let target_samples = self.render_quality.samples_per_pixel();
+        let pixel_count = region.width.get() as u64 * region.height.get() as u64;
+
+        let mut accumulator = self.accumulator(region);
+
+        let mut info = PassInfo {
+            pass: 0,
+            samples_per_pixel: 0,
+            target_samples_per_pixel: target_samples,
+            total_samples: 0,
+            elapsed: start.elapsed(),
+        };
+
+        let stop_reason = loop {
+            if info.samples_per_pixel >= target_samples {
+                break StopReason::Completed;
+            }
 
-        for _ in 0..self.render_quality.samples_per_pixel() {
-            let ray = self.get_ray(x, y);
-            pixel_color += self.ray_color(self.render_quality.max_depth(), ray, world);
+            let samples = samples_per_pass.get().min(target_samples - info.samples_per_pixel);
+            let samples = control.budget.pass_samples(samples, pixel_count, info.total_samples);
+
+            if samples == 0 {
+                break StopReason::SampleLimit;
+            }
+
+            let rendered = AtomicU64::new(0);
+            let adaptive = self.render_quality.adaptive();
+
+            self.render_tiles(region, &mut accumulator, control, start, info.pass + 1, 0, |x, y, pixel, splats, aov| {
+                if adaptive.is_some_and(|adaptive| adaptive.is_converged(pixel)) {
+                    return;
+                }
+
+                *pixel += self.sample_pixel(&scene, integrator, x, y, pixel.samples..pixel.samples + samples, splats, aov);
+                rendered.fetch_add(samples as u64, Ordering::Relaxed);
+            });
+
+            let rendered = rendered.into_inner();
+
+            if let Some(reason) = control.interruption(start) {
+                info.total_samples += rendered;
+                break reason;
+            }
+
+            // only happens once every pixel has converged
+            if rendered == 0 {
+                break StopReason::Completed;
+            }
+
+            info.pass += 1;
+            info.samples_per_pixel += samples;
+            info.total_samples += rendered;
+            info.elapsed = start.elapsed();
+
+            if on_pass(&info, &accumulator).is_break() {
+                break StopReason::Cancelled;
+            }
+
+            if control.budget.noise_threshold.is_some_and(|threshold| accumulator.noise() <= threshold) {
+                break StopReason::NoiseThreshold;
+            }
+        };
+
+        RenderOutcome {
+            image: accumulator.snapshot(),
+            sample_counts: accumulator.sample_counts(),
+            aovs: accumulator.aovs(self.aovs),
+            stop_reason,
+            total_samples: info.total_samples,
+            elapsed: start.elapsed(),
         }
+    }
 
-        transform_color_to_pixel(self.render_quality.pixel_samples_scale() * pixel_color)
+    // hands the region's tiles out to rayon's threads one at a time in the configured order, calling render_pixel with
+    // the absolute coordinates of every pixel; tiles in progress when the control interrupts the render are left unfinished.
+    // when splatting, every tile gets its own buffer, which are all added once the tiles are done, in the tiles' order
+    #[expect(clippy::too_many_arguments, reason = "internal render loop")]
+    fn render_tiles(
+        &self,
+        region: PixelRegion,
+        accumulator: &mut Accumulator,
+        control: &RenderControl,
+        start: Instant,
+        pass: u32,
+        debug_frequency: u8,
+        render_pixel: impl Fn(u32, u32, &mut AccumulatedPixel, Option<&mut SplatBuffer>, Option<&mut AovPixel>) + Sync,
+    ) {
+        let tiles = self.tiles.tiles(region);
+
+        let next_tile = AtomicUsize::new(0);
+        let completed = AtomicUsize::new(0);
+
+        // tiles never overlap, the lock is only held to copy a tile's pixels in and out
+        let accumulator = Mutex::new(accumulator);
+        let tile_splats = Mutex::new(Vec::new());
+
+        (0..rayon::current_num_threads()).into_par_iter().for_each(|_| {
+            loop {
+                let index = next_tile.fetch_add(1, Ordering::Relaxed);
+
+                let Some(&tile) = tiles.get(index) else {
+                    break;
+                };
+
+                let tile_pixels = || (tile.y..tile.y + tile.height.get())
+                    .flat_map(move |y| (tile.x..tile.x + tile.width.get()).map(move |x| (x, y)));
+
+                let (mut pixels, mut aovs): (Vec<AccumulatedPixel>, Vec<Option<AovPixel>>) = {
+                    let accumulator = accumulator.lock().expect("render threads don't panic");
+
+                    tile_pixels()
+                        .map(|(x, y)| (*accumulator.pixel(x - region.x, y - region.y), accumulator.aov_pixel(x - region.x, y - region.y).copied()))
+                        .unzip()
+                };
+
+                let mut splats = self.splat_buffer(tile, region);
+                let mut interrupted = false;
+
+                for (((x, y), pixel), aov) in tile_pixels().zip(&mut pixels).zip(&mut aovs) {
+                    if control.interruption(start).is_some() {
+                        interrupted = true;
+                        break;
+                    }
+
+                    render_pixel(x, y, pixel, splats.as_mut(), aov.as_mut());
+                }
+
+                {
+                    let mut accumulator = accumulator.lock().expect("render threads don't panic");
+
+                    for (((x, y), pixel), aov) in tile_pixels().zip(pixels).zip(aovs) {
+                        *accumulator.pixel_mut(x - region.x, y - region.y) = pixel;
+
+                        if let (Some(target), Some(aov)) = (accumulator.aov_pixel_mut(x - region.x, y - region.y), aov) {
+                            *target = aov;
+                        }
+                    }
+                }
+
+                if let Some(splats) = splats {
+                    tile_splats.lock().expect("render threads don't panic").push((index, splats));
+                }
+
+                if interrupted {
+                    return;
+                }
+
+                let completed = completed.fetch_add(1, Ordering::Relaxed) + 1;
+
+                if let Some(on_tile) = &control.on_tile {
+                    on_tile(&TileProgress { tile, pass, completed, total: tiles.len() });
+                }
+
+                if debug_frequency != 0 && completed.is_multiple_of(debug_frequency as usize) {
+                    println!("Tiles completed: {completed}/{}", tiles.len());
+                }
+            }
+        });
+
+        let accumulator = accumulator.into_inner().expect("render threads don't panic");
+        let mut tile_splats = tile_splats.into_inner().expect("render threads don't panic");
+        tile_splats.sort_by_key(|(index, _)| *index);
+
+        for (_, splats) in tile_splats {
+            accumulator.add_splats(region, &splats);
+        }
     }
 
-    fn get_ray(&self, x :u32, y: u32) -> Ray {
-        let offset_x = rand::random_range(Self::RANDOM_RAY_OFFSET_RANGE);
-        let offset_y = rand::random_range(Self::RANDOM_RAY_OFFSET_RANGE);
+    // AOVs other than the sample count need their own sums for every pixel
+    fn accumulator(&self, region: PixelRegion) -> Accumulator {
+        let accumulator = Accumulator::new(region.width.get(), region.height.get());
 
-        let pixel_sample = self.pixel00_loc
-            + ((x as f32 + offset_x) * self.pixel_delta_u)
-            + ((y as f32 + offset_y) * self.pixel_delta_v);
+        if self.aovs.iter().any(Aov::needs_samples) { accumulator.with_aovs() } else { accumulator }
+    }
 
-        let origin = if self.defocus_angle <= 0.0 { self.center } else { self.defocus_disk_sample() };
+    // only splatted renders need a buffer, covering the tile and every pixel its samples reach within the region
+    fn splat_buffer(&self, tile: PixelRegion, region: PixelRegion) -> Option<SplatBuffer> {
+        (self.filter.mode == FilterMode::Splatted).then(|| SplatBuffer::around(tile, region, self.filter.radius))
+    }
 
-        Ray { origin, direction: pixel_sample - origin }
+    fn scene_context<'a>(&self, world: &'a impl Hittable, lights: &'a HittableList) -> SceneContext<'a> {
+        SceneContext {
+            world,
+            lights,
+            background_color: self.background_color,
+            max_depth: self.render_quality.max_depth(),
+            min_depth: self.render_quality.min_depth(),
+            // numbering the materials means visiting the whole scene, so it's only done when the ids are wanted
+            material_ids: if self.aovs.contains(Aov::MaterialId) { MaterialIds::new(world) } else { MaterialIds::default() },
+        }
     }
 
-    fn ray_color(&self, depth: u16, ray: Ray, world: &impl Hittable) -> Color {
-        if depth == 0 {
-            return Vec3::zeros();
+    // when sampling adaptively, pixels stop short of max_samples once they've converged
+    #[expect(clippy::too_many_arguments, reason = "internal render loop")]
+    fn sample_pixel_adaptive(
+        &self,
+        scene: &SceneContext,
+        integrator: &dyn Integrator,
+        x: u32,
+        y: u32,
+        max_samples: u32,
+        mut splats: Option<&mut SplatBuffer>,
+        mut aov: Option<&mut AovPixel>,
+    ) -> AccumulatedPixel {
+        let Some(adaptive) = self.render_quality.adaptive() else {
+            return self.sample_pixel(scene, integrator, x, y, 0..max_samples, splats, aov);
+        };
+
+        let min_samples = adaptive.min_samples_per_pixel.min(max_samples);
+        let mut pixel = self.sample_pixel(scene, integrator, x, y, 0..min_samples, splats.as_deref_mut(), aov.as_deref_mut());
+
+        while pixel.samples < max_samples && !adaptive.is_converged(&pixel) {
+            let samples = Self::ADAPTIVE_BATCH_SAMPLES.min(max_samples - pixel.samples);
+            pixel += self.sample_pixel(scene, integrator, x, y, pixel.samples..pixel.samples + samples, splats.as_deref_mut(), aov.as_deref_mut());
         }
 
-        if let Some(rec) = world.hit(ray, Interval { min: Self::MIN_RAY_DISTANCE, max: f32::INFINITY }) {
-            let emission_color = rec.material.emitted(rec.uv, rec.p);
+        pixel
+    }
 
-            if let Some(ScatterResult { attenuation, scattered }) = rec.material.scatter(ray, &rec) {
-                let ray_color = self.ray_color(depth - 1, scattered, world);
+    // samples are indexed per pixel, and each one's coordinates only depend on the sampler, the pixel and that index.
+    // importance sampled filters weight the samples kept in the pixel, splatted ones spread them over the buffer instead,
+    // the pixel's own samples are then unweighted and only used for its statistics. AOVs are never filtered
+    #[expect(clippy::too_many_arguments, reason = "internal render loop")]
+    fn sample_pixel(
+        &self,
+        scene: &SceneContext,
+        integrator: &dyn Integrator,
+        x: u32,
+        y: u32,
+        indices: Range<u32>,
+        mut splats: Option<&mut SplatBuffer>,
+        mut aov: Option<&mut AovPixel>,
+    ) -> AccumulatedPixel {
+        let mut pixel = AccumulatedPixel::default();
+
+        for index in indices {
+            let mut sampler = SampleStream::new(self.sampler.as_ref(), x, y, index);
+
+            let (offset, weight) = match self.filter.mode {
+                FilterMode::ImportanceSampled => self.filter_sampler.sample(sampler.get_2d()),
+                FilterMode::Splatted => (sampler.get_2d() - Vec2::from_element(0.5), 1.0),
+            };
+
+            let sample = match self.get_ray(x, y, offset, &mut sampler) {
+                Some(ray) => integrator.trace(ray, scene, &mut sampler),
+                None => PathSample {
+                    radiance: Color::zeros(),
+                    direct: Color::zeros(),
+                    bounces: 0,
+                    termination: Termination::NoRay,
+                    first_hit: None,
+                },
+            };
+
+            let radiance = sample.radiance;
+
+            if let Some(aov) = aov.as_deref_mut() {
+                aov.add_sample(&sample);
+            }
 
-                emission_color + attenuation.component_mul(&ray_color)
-            } else {
-                emission_color
+            if let Some(splats) = splats.as_deref_mut() {
+                splats.splat(&self.filter, Vec2::new(x as f32 + 0.5, y as f32 + 0.5) + offset, radiance);
             }
-        } else {
-            self.background_color
+
+            pixel.add_sample(weight * radiance);
         }
+
+        pixel
     }
 
-    fn defocus_disk_sample(&self) -> Vec3 {
-        let v = random_vec_in_unit_disk();
-        self.center + (v.x * self.defocus_disk_u) + (v.y * self.defocus_disk_v)
+    // always draws the lens and shutter dimensions, so the integrator's dimensions line up between cameras with and
+    // without defocus or motion blur. None where the model doesn't cover the image
+    fn get_ray(&self, x :u32, y: u32, offset: Vec2, sampler: &mut SampleStream) -> Option<Ray> {
+        let lens = sampler.get_2d();
+        let time = self.shutter.time(sampler.get_1d());
+
+        let film = Vec2::new(
+            (x as f32 + 0.5 + offset.x) / self.dimensions.width.get() as f32,
+            (y as f32 + 0.5 + offset.y) / self.dimensions.height.get() as f32,
+        );
+
+        self.model.generate_ray(film, lens, time)
     }
 }
\ No newline at end of file
@@ -0,0 +1,47 @@
+use glm::{Vec2, Vec3};
+use crate::camera::model::{CameraFrame, CameraModel};
+use crate::ray::Ray;
+
+// the six 90° views of a cube around the camera, in a 3x2 grid of right, left, up / down, front, back; for 3:2 images
+pub struct CubemapCamera {
+    frame: CameraFrame,
+}
+
+impl CubemapCamera {
+    // forward, right and up of each face in camera space, in grid order, oriented as if the camera turned to face it
+    const FACES: [(Vec3, Vec3, Vec3); 6] = {
+        const RIGHT: Vec3 = Vec3::new(1.0, 0.0, 0.0);
+        const LEFT: Vec3 = Vec3::new(-1.0, 0.0, 0.0);
+        const UP: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        const DOWN: Vec3 = Vec3::new(0.0, -1.0, 0.0);
+        const FRONT: Vec3 = Vec3::new(0.0, 0.0, -1.0);
+        const BACK: Vec3 = Vec3::new(0.0, 0.0, 1.0);
+
+        [
+            (RIGHT, BACK, UP),
+            (LEFT, FRONT, UP),
+            (UP, RIGHT, BACK),
+            (DOWN, RIGHT, FRONT),
+            (FRONT, RIGHT, UP),
+            (BACK, LEFT, UP),
+        ]
+    };
+
+    pub fn new(frame: CameraFrame) -> Self {
+        Self { frame }
+    }
+}
+
+impl CameraModel for CubemapCamera {
+    fn generate_ray(&self, film: Vec2, _lens: Vec2, time: f32) -> Option<Ray> {
+        let (column, row) = ((film.x * 3.0).floor().clamp(0.0, 2.0), (film.y * 2.0).floor().clamp(0.0, 1.0));
+
+        // position on the face, in [-1, 1]² from the bottom left
+        let a = 2.0 * (film.x * 3.0 - column) - 1.0;
+        let b = 1.0 - 2.0 * (film.y * 2.0 - row);
+
+        let (forward, right, up) = Self::FACES[row as usize * 3 + column as usize];
+
+        Some(Ray { origin: self.frame.origin, direction: self.frame.to_world(forward + a * right + b * up), time })
+    }
+}
@@ -0,0 +1,30 @@
+use std::f32::consts::{PI, TAU};
+use glm::{Vec2, Vec3};
+use crate::camera::model::{CameraFrame, CameraModel};
+use crate::ray::Ray;
+
+// every direction around the camera, longitude across the width and latitude down the height; for 2:1 images
+pub struct EquirectangularCamera {
+    frame: CameraFrame,
+}
+
+impl EquirectangularCamera {
+    pub fn new(frame: CameraFrame) -> Self {
+        Self { frame }
+    }
+}
+
+impl CameraModel for EquirectangularCamera {
+    fn generate_ray(&self, film: Vec2, _lens: Vec2, time: f32) -> Option<Ray> {
+        let longitude = (film.x - 0.5) * TAU;
+        let latitude = (0.5 - film.y) * PI;
+
+        let direction = Vec3::new(
+            latitude.cos() * longitude.sin(),
+            latitude.sin(),
+            -latitude.cos() * longitude.cos(),
+        );
+
+        Some(Ray { origin: self.frame.origin, direction: self.frame.to_world(direction), time })
+    }
+}
@@ -0,0 +1,59 @@
+use glm::{Vec2, Vec3};
+use crate::camera::model::{CameraFrame, CameraModel};
+use crate::ray::Ray;
+
+// how the angle from the view direction maps to the distance from the image's center
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FisheyeMapping {
+    // proportional to the angle
+    #[default]
+    Equidistant,
+    // equal areas of the image cover equal solid angles
+    Equisolid,
+}
+
+impl FisheyeMapping {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "equidistant" => Some(Self::Equidistant),
+            "equisolid" => Some(Self::Equisolid),
+            _ => None,
+        }
+    }
+}
+
+// a round image in the largest circle that fits, covering fov degrees across, up to 360
+pub struct FisheyeCamera {
+    frame: CameraFrame,
+    max_angle: f32,
+    mapping: FisheyeMapping,
+    aspect_ratio: f32,
+}
+
+impl FisheyeCamera {
+    pub fn new(frame: CameraFrame, fov: f32, mapping: FisheyeMapping, aspect_ratio: f32) -> Self {
+        Self { frame, max_angle: fov.to_radians() / 2.0, mapping, aspect_ratio }
+    }
+}
+
+impl CameraModel for FisheyeCamera {
+    fn generate_ray(&self, film: Vec2, _lens: Vec2, time: f32) -> Option<Ray> {
+        // relative to the circle's radius
+        let p = Vec2::new((film.x - 0.5) * self.aspect_ratio, 0.5 - film.y) / (self.aspect_ratio.min(1.0) / 2.0);
+        let radius = p.magnitude();
+
+        if radius > 1.0 {
+            return None;
+        }
+
+        let angle = match self.mapping {
+            FisheyeMapping::Equidistant => radius * self.max_angle,
+            FisheyeMapping::Equisolid => 2.0 * (radius * (self.max_angle / 2.0).sin()).asin(),
+        };
+
+        let xy = if radius > 0.0 { angle.sin() * p / radius } else { Vec2::zeros() };
+        let direction = Vec3::new(xy.x, xy.y, -angle.cos());
+
+        Some(Ray { origin: self.frame.origin, direction: self.frame.to_world(direction), time })
+    }
+}
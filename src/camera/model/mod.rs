@@ -0,0 +1,70 @@
+pub mod perspective;
+pub mod thin_lens;
+pub mod orthographic;
+pub mod equirectangular;
+pub mod cubemap;
+pub mod fisheye;
+
+use glm::{Vec2, Vec3};
+use crate::ray::Ray;
+
+// turns points on the image into camera rays. the camera's render loop draws the points, so every model gets the same
+// sampling, filtering, AOVs and parallel rendering
+pub trait CameraModel: Send + Sync {
+    // film is the point on the image, in [0, 1]² from the top left, and lens a point in [0, 1)² for models with an
+    // aperture. None where the model doesn't cover the image, which leaves it black
+    fn generate_ray(&self, film: Vec2, lens: Vec2, time: f32) -> Option<Ray>;
+}
+
+// where a camera is and which way it faces
+#[derive(Copy, Clone, Debug)]
+pub struct CameraFrame {
+    pub origin: Vec3,
+    // right, up and backwards
+    pub u: Vec3,
+    pub v: Vec3,
+    pub w: Vec3,
+}
+
+impl CameraFrame {
+    const UP_VECTOR: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+
+    pub fn look_at(origin: Vec3, target: Vec3) -> Self {
+        let w = (origin - target).normalize();
+        let u = Self::UP_VECTOR.cross(&w).normalize();
+        let v = w.cross(&u);
+
+        Self { origin, u, v, w }
+    }
+
+    // from camera space, with x right, y up and looking down -z
+    pub fn to_world(&self, direction: Vec3) -> Vec3 {
+        direction.x * self.u + direction.y * self.v + direction.z * self.w
+    }
+}
+
+// the rectangle in front of the camera that the image covers
+#[derive(Copy, Clone, Debug)]
+pub struct Viewport {
+    upper_left: Vec3,
+    // across the whole viewport, left to right and top to bottom
+    horizontal: Vec3,
+    vertical: Vec3,
+}
+
+impl Viewport {
+    pub fn new(frame: &CameraFrame, distance: f32, height: f32, aspect_ratio: f32) -> Self {
+        let horizontal = height * aspect_ratio * frame.u;
+        let vertical = height * -frame.v;
+
+        Self {
+            upper_left: frame.origin - distance * frame.w - horizontal / 2.0 - vertical / 2.0,
+            horizontal,
+            vertical,
+        }
+    }
+
+    pub fn at(&self, film: Vec2) -> Vec3 {
+        self.upper_left + film.x * self.horizontal + film.y * self.vertical
+    }
+}
@@ -0,0 +1,35 @@
+use glm::Vec2;
+use crate::camera::focus_settings::FocusSettings;
+use crate::camera::model::{CameraFrame, CameraModel, Viewport};
+use crate::camera::model::thin_lens::{lens_point, lens_radius};
+use crate::ray::Ray;
+
+// parallel rays, so sizes don't change with distance. with a defocus angle, the lens is centered straight behind each
+// point of the viewport, which lies on the focus plane
+pub struct OrthographicCamera {
+    frame: CameraFrame,
+    viewport: Viewport,
+    focus_dist: f32,
+    lens_radius: f32,
+}
+
+impl OrthographicCamera {
+    // view_height is in world units
+    pub fn new(frame: CameraFrame, view_height: f32, aspect_ratio: f32, focus: FocusSettings) -> Self {
+        Self {
+            frame,
+            viewport: Viewport::new(&frame, focus.focus_dist, view_height, aspect_ratio),
+            focus_dist: focus.focus_dist,
+            lens_radius: lens_radius(focus),
+        }
+    }
+}
+
+impl CameraModel for OrthographicCamera {
+    fn generate_ray(&self, film: Vec2, lens: Vec2, time: f32) -> Option<Ray> {
+        let target = self.viewport.at(film);
+        let origin = lens_point(&self.frame, target + self.focus_dist * self.frame.w, self.lens_radius, lens);
+
+        Some(Ray { origin, direction: target - origin, time })
+    }
+}
@@ -0,0 +1,24 @@
+use glm::Vec2;
+use crate::camera::model::{CameraFrame, CameraModel, Viewport};
+use crate::ray::Ray;
+
+// a pinhole camera, everything is in focus
+pub struct PerspectiveCamera {
+    frame: CameraFrame,
+    viewport: Viewport,
+}
+
+impl PerspectiveCamera {
+    // fov is vertical, in degrees
+    pub fn new(frame: CameraFrame, fov: f32, aspect_ratio: f32) -> Self {
+        let height = 2.0 * f32::tan(fov.to_radians() / 2.0);
+
+        Self { frame, viewport: Viewport::new(&frame, 1.0, height, aspect_ratio) }
+    }
+}
+
+impl CameraModel for PerspectiveCamera {
+    fn generate_ray(&self, film: Vec2, _lens: Vec2, time: f32) -> Option<Ray> {
+        Some(Ray { origin: self.frame.origin, direction: self.viewport.at(film) - self.frame.origin, time })
+    }
+}
@@ -0,0 +1,44 @@
+use glm::{Vec2, Vec3};
+use crate::camera::focus_settings::FocusSettings;
+use crate::camera::model::{CameraFrame, CameraModel, Viewport};
+use crate::ray::Ray;
+use crate::util::warp::concentric_disk;
+
+// a perspective camera with an aperture, only the plane at the focus distance is sharp
+pub struct ThinLensCamera {
+    frame: CameraFrame,
+    // on the focus plane, where rays from every point of the lens meet
+    viewport: Viewport,
+    lens_radius: f32,
+}
+
+impl ThinLensCamera {
+    // fov is vertical, in degrees
+    pub fn new(frame: CameraFrame, fov: f32, aspect_ratio: f32, focus: FocusSettings) -> Self {
+        let height = 2.0 * f32::tan(fov.to_radians() / 2.0) * focus.focus_dist;
+
+        Self {
+            frame,
+            viewport: Viewport::new(&frame, focus.focus_dist, height, aspect_ratio),
+            lens_radius: lens_radius(focus),
+        }
+    }
+}
+
+impl CameraModel for ThinLensCamera {
+    fn generate_ray(&self, film: Vec2, lens: Vec2, time: f32) -> Option<Ray> {
+        let origin = lens_point(&self.frame, self.frame.origin, self.lens_radius, lens);
+
+        Some(Ray { origin, direction: self.viewport.at(film) - origin, time })
+    }
+}
+
+// the defocus angle is the cone of rays that meet at a point on the focus plane
+pub(super) fn lens_radius(focus: FocusSettings) -> f32 {
+    focus.focus_dist * f32::tan(f32::to_radians(focus.defocus_angle.max(0.0) / 2.0))
+}
+
+pub(super) fn lens_point(frame: &CameraFrame, center: Vec3, radius: f32, lens: Vec2) -> Vec3 {
+    let p = radius * concentric_disk(lens);
+    center + p.x * frame.u + p.y * frame.v
+}
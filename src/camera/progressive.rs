@@ -0,0 +1,166 @@
+use std::ops::AddAssign;
+use std::time::Duration;
+use image::{ImageBuffer, Luma, Rgb, Rgb32FImage};
+use crate::camera::aov::{Aov, AovPixel, AovSet};
+use crate::camera::filter::{SplatBuffer, SplatPixel};
+use crate::camera::region::PixelRegion;
+use crate::color::{Color, luminance};
+
+pub type SampleCountMap = ImageBuffer<Luma<u32>, Vec<u32>>;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AccumulatedPixel {
+    pub sum: Color,
+    // of the samples' luminance, for estimating noise
+    pub sum_squares: f32,
+    pub samples: u32,
+}
+
+impl AccumulatedPixel {
+    // relative errors are measured against at least this much luminance, so near black pixels don't dominate
+    pub const MIN_RELATIVE_LUMINANCE: f32 = 0.01;
+
+    pub fn add_sample(&mut self, color: Color) {
+        self.sum += color;
+        self.sum_squares += luminance(color).powi(2);
+        self.samples += 1;
+    }
+
+    pub fn mean(&self) -> Color {
+        if self.samples == 0 { Color::zeros() } else { self.sum / self.samples as f32 }
+    }
+
+    // standard error of the mean luminance, relative to that mean
+    pub fn relative_error(&self) -> f32 {
+        if self.samples < 2 {
+            return f32::INFINITY;
+        }
+
+        let n = self.samples as f32;
+        let mean = luminance(self.sum) / n;
+        let variance = ((self.sum_squares - n * mean * mean) / (n - 1.0)).max(0.0);
+
+        (variance / n).sqrt() / mean.max(Self::MIN_RELATIVE_LUMINANCE)
+    }
+}
+
+impl AddAssign for AccumulatedPixel {
+    fn add_assign(&mut self, rhs: Self) {
+        self.sum += rhs.sum;
+        self.sum_squares += rhs.sum_squares;
+        self.samples += rhs.samples;
+    }
+}
+
+// running per-pixel sums, so a render can be looked at or stopped between passes
+pub struct Accumulator {
+    width: u32,
+    height: u32,
+    pub(super) pixels: Vec<AccumulatedPixel>,
+    // filtered sums when samples are splatted into their neighbors, the pixels then only keep track of their own samples
+    splats: Option<Vec<SplatPixel>>,
+    // only kept when the render has AOVs besides the sample count
+    aovs: Option<Vec<AovPixel>>,
+}
+
+impl Accumulator {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height, pixels: vec![AccumulatedPixel::default(); (width * height) as usize], splats: None, aovs: None }
+    }
+
+    pub(super) fn with_aovs(self) -> Self {
+        Self { aovs: Some(vec![AovPixel::default(); (self.width * self.height) as usize]), ..self }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> &AccumulatedPixel {
+        &self.pixels[(y * self.width + x) as usize]
+    }
+
+    pub(super) fn pixel_mut(&mut self, x: u32, y: u32) -> &mut AccumulatedPixel {
+        &mut self.pixels[(y * self.width + x) as usize]
+    }
+
+    pub(super) fn aov_pixel(&self, x: u32, y: u32) -> Option<&AovPixel> {
+        self.aovs.as_ref().map(|aovs| &aovs[(y * self.width + x) as usize])
+    }
+
+    pub(super) fn aov_pixel_mut(&mut self, x: u32, y: u32) -> Option<&mut AovPixel> {
+        self.aovs.as_mut().map(|aovs| &mut aovs[(y * self.width + x) as usize])
+    }
+
+    // region is where the accumulator's pixels are in the image the splats' coordinates refer to
+    pub(super) fn add_splats(&mut self, region: PixelRegion, buffer: &SplatBuffer) {
+        let splats = self.splats.get_or_insert_with(|| vec![SplatPixel::default(); (self.width * self.height) as usize]);
+
+        for (x, y, splat) in buffer.pixels() {
+            let pixel = &mut splats[((y - region.y) * self.width + x - region.x) as usize];
+            pixel.weighted_sum += splat.weighted_sum;
+            pixel.weight += splat.weight;
+        }
+    }
+
+    pub fn snapshot(&self) -> Rgb32FImage {
+        Rgb32FImage::from_fn(self.width, self.height, |x, y| {
+            let color = match &self.splats {
+                // filters with negative lobes can leave sparsely sampled pixels without any weight
+                Some(splats) if splats[(y * self.width + x) as usize].weight > 0.0 => {
+                    let splat = splats[(y * self.width + x) as usize];
+                    splat.weighted_sum / splat.weight
+                }
+                _ => self.pixel(x, y).mean(),
+            };
+
+            Rgb(color.into())
+        })
+    }
+
+    // None if the render didn't keep the AOV
+    pub fn aov(&self, aov: Aov) -> Option<Rgb32FImage> {
+        if aov == Aov::SampleCount {
+            return Some(Rgb32FImage::from_fn(self.width, self.height, |x, y| {
+                Rgb([self.pixel(x, y).samples as f32, 0.0, 0.0])
+            }));
+        }
+
+        self.aovs.as_ref()?;
+
+        Some(Rgb32FImage::from_fn(self.width, self.height, |x, y| {
+            let value = self.aov_pixel(x, y).and_then(|pixel| pixel.value(aov)).unwrap_or_default();
+            Rgb(value.into())
+        }))
+    }
+
+    // the ones of the set that the render kept
+    pub fn aovs(&self, aovs: AovSet) -> Vec<(Aov, Rgb32FImage)> {
+        aovs.iter()
+            .filter_map(|aov| Some((aov, self.aov(aov)?)))
+            .collect()
+    }
+
+    pub fn sample_counts(&self) -> SampleCountMap {
+        SampleCountMap::from_fn(self.width, self.height, |x, y| Luma([self.pixel(x, y).samples]))
+    }
+
+    // mean relative error over all pixels
+    pub fn noise(&self) -> f32 {
+        self.pixels.iter().map(AccumulatedPixel::relative_error).sum::<f32>() / self.pixels.len() as f32
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct PassInfo {
+    // starts at 1
+    pub pass: u32,
+    pub samples_per_pixel: u32,
+    pub target_samples_per_pixel: u32,
+    pub total_samples: u64,
+    pub elapsed: Duration,
+}
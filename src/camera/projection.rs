@@ -0,0 +1,55 @@
+use crate::camera::focus_settings::FocusSettings;
+use crate::camera::model::{CameraFrame, CameraModel};
+use crate::camera::model::cubemap::CubemapCamera;
+use crate::camera::model::equirectangular::EquirectangularCamera;
+use crate::camera::model::fisheye::{FisheyeCamera, FisheyeMapping};
+use crate::camera::model::orthographic::OrthographicCamera;
+use crate::camera::model::perspective::PerspectiveCamera;
+use crate::camera::model::thin_lens::ThinLensCamera;
+
+// the built in camera models; others can be given to the camera directly
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Projection {
+    // vertical field of view in degrees
+    Perspective { fov: f32 },
+    // parallel rays, with the height of the view in world units; sizes don't change with distance, as in technical drawings
+    Orthographic { view_height: f32 },
+    // every direction around the camera, longitude across the width and latitude down the height; for 2:1 images
+    Equirectangular,
+    // the six 90° views of a cube around the camera, in a 3x2 grid of right, left, up / down, front, back; for 3:2 images
+    Cubemap,
+    // a round image in the largest circle that fits, covering fov degrees across, up to 360
+    Fisheye { fov: f32, mapping: FisheyeMapping },
+}
+
+impl Projection {
+    // the kind of projection with its default parameters
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "perspective" => Some(Self::Perspective { fov: 90.0 }),
+            "orthographic" => Some(Self::Orthographic { view_height: 2.0 }),
+            "equirectangular" => Some(Self::Equirectangular),
+            "cubemap" => Some(Self::Cubemap),
+            "fisheye" => Some(Self::Fisheye { fov: 180.0, mapping: FisheyeMapping::default() }),
+            _ => None,
+        }
+    }
+
+    // a perspective camera only gets a lens with a defocus angle, panoramic ones never have one
+    pub fn build(&self, frame: CameraFrame, aspect_ratio: f32, focus: FocusSettings) -> Box<dyn CameraModel> {
+        match *self {
+            Self::Perspective { fov } if focus.defocus_angle > 0.0 => Box::new(ThinLensCamera::new(frame, fov, aspect_ratio, focus)),
+            Self::Perspective { fov } => Box::new(PerspectiveCamera::new(frame, fov, aspect_ratio)),
+            Self::Orthographic { view_height } => Box::new(OrthographicCamera::new(frame, view_height, aspect_ratio, focus)),
+            Self::Equirectangular => Box::new(EquirectangularCamera::new(frame)),
+            Self::Cubemap => Box::new(CubemapCamera::new(frame)),
+            Self::Fisheye { fov, mapping } => Box::new(FisheyeCamera::new(frame, fov, mapping, aspect_ratio)),
+        }
+    }
+}
+
+impl Default for Projection {
+    fn default() -> Self {
+        Self::Perspective { fov: 90.0 }
+    }
+}
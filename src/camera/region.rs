@@ -0,0 +1,21 @@
+use std::num::NonZeroU32;
+use crate::camera::dimensions::CameraDimensions;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PixelRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: NonZeroU32,
+    pub height: NonZeroU32,
+}
+
+impl PixelRegion {
+    pub fn full(dimensions: &CameraDimensions) -> Self {
+        Self { x: 0, y: 0, width: dimensions.width, height: dimensions.height }
+    }
+
+    pub fn fits_within(&self, dimensions: &CameraDimensions) -> bool {
+        self.x.checked_add(self.width.get()).is_some_and(|right| right <= dimensions.width.get())
+            && self.y.checked_add(self.height.get()).is_some_and(|bottom| bottom <= dimensions.height.get())
+    }
+}
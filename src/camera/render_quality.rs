@@ -1,12 +1,46 @@
+use crate::camera::progressive::AccumulatedPixel;
+use crate::sampler::SamplerKind;
+
+#[derive(Copy, Clone, Debug)]
 pub struct RenderQuality {
+    // the most any pixel gets when sampling adaptively
     pub samples_per_pixel: u32,
     pub max_depth: u16,
+    // bounces before paths start being randomly terminated by russian roulette
+    pub min_depth: u16,
+    pub adaptive: Option<AdaptiveSampling>,
+    // renders with the same seed and settings are identical, however many threads render them
+    pub seed: u64,
+    pub sampler: SamplerKind,
 }
 
 impl RenderQuality {
-    pub const LOW: RenderQuality = RenderQuality { samples_per_pixel: 50, max_depth: 10 };
-    pub const MEDIUM: RenderQuality = RenderQuality { samples_per_pixel: 100, max_depth: 20 };
-    pub const HIGH: RenderQuality = RenderQuality { samples_per_pixel: 500, max_depth: 50 };
+    pub const LOW: RenderQuality = RenderQuality { samples_per_pixel: 50, max_depth: 10, min_depth: 3, adaptive: None, seed: 0, sampler: SamplerKind::Sobol };
+    pub const MEDIUM: RenderQuality = RenderQuality { samples_per_pixel: 100, max_depth: 20, min_depth: 4, adaptive: None, seed: 0, sampler: SamplerKind::Sobol };
+    pub const HIGH: RenderQuality = RenderQuality { samples_per_pixel: 500, max_depth: 50, min_depth: 5, adaptive: None, seed: 0, sampler: SamplerKind::Sobol };
+}
+
+// pixels stop being sampled once the confidence interval of their mean is narrow enough
+#[derive(Copy, Clone, Debug)]
+pub struct AdaptiveSampling {
+    pub min_samples_per_pixel: u32,
+    // half width of the 95% confidence interval, relative to the pixel's mean luminance
+    pub error_threshold: f32,
+}
+
+impl AdaptiveSampling {
+    const CONFIDENCE_Z: f32 = 1.96;
+
+    pub fn is_converged(&self, pixel: &AccumulatedPixel) -> bool {
+        pixel.samples >= self.min_samples_per_pixel
+            && Self::CONFIDENCE_Z * pixel.relative_error() <= self.error_threshold
+    }
+}
+
+impl Default for AdaptiveSampling {
+    fn default() -> Self {
+        Self { min_samples_per_pixel: 16, error_threshold: 0.05 }
+    }
 }
 
 impl Default for RenderQuality {
@@ -17,16 +51,18 @@ impl Default for RenderQuality {
 
 pub(super) struct InternalRenderQuality {
     samples_per_pixel: u32,
-    pixel_samples_scale: f32,
     max_depth: u16,
+    min_depth: u16,
+    adaptive: Option<AdaptiveSampling>,
 }
 
 impl From<RenderQuality> for InternalRenderQuality {
     fn from(value: RenderQuality) -> Self {
         Self {
             samples_per_pixel: value.samples_per_pixel,
-            pixel_samples_scale: (value.samples_per_pixel as f32).recip(),
             max_depth: value.max_depth,
+            min_depth: value.min_depth,
+            adaptive: value.adaptive,
         }
     }
 }
@@ -36,11 +72,15 @@ impl InternalRenderQuality {
         self.samples_per_pixel
     }
 
-    pub fn pixel_samples_scale(&self) -> f32 {
-        self.pixel_samples_scale
-    }
-
     pub fn max_depth(&self) -> u16 {
         self.max_depth
     }
+
+    pub fn min_depth(&self) -> u16 {
+        self.min_depth
+    }
+
+    pub fn adaptive(&self) -> Option<AdaptiveSampling> {
+        self.adaptive
+    }
 }
@@ -0,0 +1,71 @@
+use glm::Vec3;
+use crate::camera::Camera;
+use crate::camera::aov::AovSet;
+use crate::camera::dimensions::CameraDimensions;
+use crate::camera::filter::PixelFilter;
+use crate::camera::focus_settings::FocusSettings;
+use crate::camera::model::CameraFrame;
+use crate::camera::projection::Projection;
+use crate::camera::render_quality::RenderQuality;
+use crate::camera::shutter::Shutter;
+use crate::camera::tiles::TileSettings;
+use crate::color::Color;
+use crate::output::tone_map::ToneMapping;
+
+#[derive(Copy, Clone, Debug)]
+pub struct CameraSettings {
+    pub center: Vec3,
+    pub look_at: Vec3,
+    pub background_color: Color,
+    pub dimensions: CameraDimensions,
+    pub render_quality: RenderQuality,
+    pub focus_settings: FocusSettings,
+    pub projection: Projection,
+    pub tone_mapping: ToneMapping,
+    pub tiles: TileSettings,
+    pub filter: PixelFilter,
+    pub aovs: AovSet,
+    pub shutter: Shutter,
+}
+
+impl CameraSettings {
+    pub fn build(&self) -> Camera {
+        Camera::new(
+            self.center,
+            self.look_at,
+            self.background_color,
+            self.dimensions,
+            self.render_quality,
+            self.focus_settings,
+            self.projection,
+            self.tiles,
+            self.filter,
+            self.aovs,
+            self.shutter,
+        )
+    }
+
+    // where the camera is and which way it faces, to build custom models with
+    pub fn frame(&self) -> CameraFrame {
+        CameraFrame::look_at(self.center, self.look_at)
+    }
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            center: Vec3::zeros(),
+            look_at: Vec3::new(0.0, 0.0, -1.0),
+            background_color: Color::zeros(),
+            dimensions: CameraDimensions::default(),
+            render_quality: RenderQuality::default(),
+            focus_settings: FocusSettings::default(),
+            projection: Projection::default(),
+            tone_mapping: ToneMapping::default(),
+            tiles: TileSettings::default(),
+            filter: PixelFilter::default(),
+            aovs: AovSet::default(),
+            shutter: Shutter::default(),
+        }
+    }
+}
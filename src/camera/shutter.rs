@@ -0,0 +1,19 @@
+// the span of scene time an image is exposed for. rays are cast at times spread evenly over it, so objects that move
+// within it are blurred along their path
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Shutter {
+    pub open: f32,
+    pub close: f32,
+}
+
+impl Shutter {
+    pub fn time(&self, u: f32) -> f32 {
+        self.open + u * (self.close - self.open)
+    }
+}
+
+impl Default for Shutter {
+    fn default() -> Self {
+        Self { open: 0.0, close: 0.0 }
+    }
+}
@@ -0,0 +1,124 @@
+use std::num::NonZeroU32;
+use crate::camera::region::PixelRegion;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TileOrder {
+    // row by row, top to bottom
+    #[default]
+    Scanline,
+    // outwards from the center, where the subject usually is
+    Spiral,
+    // along a space-filling curve, so consecutive tiles are always neighbours
+    Hilbert,
+}
+
+impl TileOrder {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "scanline" => Some(Self::Scanline),
+            "spiral" => Some(Self::Spiral),
+            "hilbert" => Some(Self::Hilbert),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct TileSettings {
+    pub size: NonZeroU32,
+    pub order: TileOrder,
+}
+
+impl TileSettings {
+    pub const DEFAULT_SIZE: NonZeroU32 = NonZeroU32::new(32).expect("nonzero");
+
+    // splits the region into tiles in render order; tiles on the right and bottom edges may be smaller
+    pub fn tiles(&self, region: PixelRegion) -> Vec<PixelRegion> {
+        let size = self.size.get();
+        let columns = region.width.get().div_ceil(size);
+        let rows = region.height.get().div_ceil(size);
+
+        let mut grid: Vec<(u32, u32)> = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (column, row)))
+            .collect();
+
+        match self.order {
+            TileOrder::Scanline => {}
+            TileOrder::Spiral => {
+                let center_x = (columns - 1) as f32 / 2.0;
+                let center_y = (rows - 1) as f32 / 2.0;
+
+                // ring by ring, going around each ring by angle
+                grid.sort_by(|&(ax, ay), &(bx, by)| {
+                    let key = |x: u32, y: u32| {
+                        let dx = x as f32 - center_x;
+                        let dy = y as f32 - center_y;
+                        (dx.abs().max(dy.abs()), dy.atan2(dx))
+                    };
+
+                    key(ax, ay).partial_cmp(&key(bx, by)).expect("tile keys are finite")
+                });
+            }
+            TileOrder::Hilbert => {
+                let n = columns.max(rows).next_power_of_two();
+                grid.sort_by_key(|&(x, y)| hilbert_index(n, x, y));
+            }
+        }
+
+        grid.into_iter()
+            .map(|(column, row)| {
+                let x = column * size;
+                let y = row * size;
+
+                PixelRegion {
+                    x: region.x + x,
+                    y: region.y + y,
+                    width: NonZeroU32::new(size.min(region.width.get() - x)).expect("tile lies within the region"),
+                    height: NonZeroU32::new(size.min(region.height.get() - y)).expect("tile lies within the region"),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for TileSettings {
+    fn default() -> Self {
+        Self { size: Self::DEFAULT_SIZE, order: TileOrder::default() }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct TileProgress {
+    pub tile: PixelRegion,
+    // progressive renders go over every tile once per pass, starting at 1; other renders only have one pass
+    pub pass: u32,
+    pub completed: usize,
+    pub total: usize,
+}
+
+// distance along the hilbert curve filling an n by n grid, n being a power of two
+fn hilbert_index(n: u32, mut x: u32, mut y: u32) -> u64 {
+    let mut index = 0;
+    let mut s = n / 2;
+
+    while s > 0 {
+        let rx = u32::from(x & s > 0);
+        let ry = u32::from(y & s > 0);
+
+        index += s as u64 * s as u64 * ((3 * rx) ^ ry) as u64;
+
+        // rotate the quadrant so the curve stays continuous
+        if ry == 0 {
+            if rx == 1 {
+                x = s - 1 - (x & (s - 1));
+                y = s - 1 - (y & (s - 1));
+            }
+
+            std::mem::swap(&mut x, &mut y);
+        }
+
+        s /= 2;
+    }
+
+    index
+}
@@ -3,11 +3,41 @@ use crate::interval::Interval;
 
 pub type Color = Vec3;
 
+// how the values stored in an image relate to linear light
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+    // colors meant to be looked at, like albedo maps
+    Srgb,
+    // data that has to be used as-is, like normal or roughness maps
+    Linear,
+}
+
+// relative luminance of linear rec. 709 primaries
+pub fn luminance(color: Color) -> f32 {
+    0.2126 * color.x + 0.7152 * color.y + 0.0722 * color.z
+}
+
+pub fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.0031308 {
+        12.92 * v
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+pub fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
 pub fn transform_color_to_pixel(color: Color) -> image::Rgb<u8> {
     const INTENSITY: Interval = Interval { min: 0.0, max: 0.999 };
 
     let transformed_color = color.map(|v|
-        INTENSITY.clamp(v.max(0.0).sqrt()) * 256.0
+        INTENSITY.clamp(linear_to_srgb(v.max(0.0))) * 256.0
     );
 
     image::Rgb([
@@ -15,4 +45,4 @@ pub fn transform_color_to_pixel(color: Color) -> image::Rgb<u8> {
         transformed_color.y as u8,
         transformed_color.z as u8,
     ])
-}
\ No newline at end of file
+}
@@ -3,7 +3,9 @@ use crate::aabb::AABB;
 use crate::hittable::{HitRecord, Hittable};
 use crate::hittable::hittable_list::HittableList;
 use crate::interval::Interval;
+use crate::material::Material;
 use crate::ray::Ray;
+use crate::sampler::SampleStream;
 
 pub struct BVHNode {
     left: Arc<dyn Hittable>,
@@ -57,24 +59,25 @@ impl BVHNode {
 }
 
 impl From<HittableList> for BVHNode {
-    fn from(mut value: HittableList) -> Self {
+    fn from(value: HittableList) -> Self {
         assert!(!value.objects.is_empty());
-        Self::new(&mut value.objects)
+        Self::new(&mut value.into_numbered_objects())
     }
 }
 
 impl Hittable for BVHNode {
-    fn hit(&self, ray: Ray, ray_t: Interval) -> Option<HitRecord> {
+    fn hit(&self, ray: Ray, ray_t: Interval, sampler: &mut SampleStream) -> Option<HitRecord> {
         if !self.aabb.hit(ray, ray_t) {
             return None;
         }
 
-        let hit_left = self.left.hit(ray, ray_t);
+        let hit_left = self.left.hit(ray, ray_t, sampler);
         let hit_right = self.right.hit(
             ray, Interval {
                 min: ray_t.min,
                 max: hit_left.as_ref().map_or(ray_t.max, |rec| rec.t)
-            }
+            },
+            sampler,
         );
 
         hit_right.or(hit_left)
@@ -83,4 +86,13 @@ impl Hittable for BVHNode {
     fn bounding_box(&self) -> &AABB {
         &self.aabb
     }
+
+    // leaves with a single object hold it on both sides
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        self.left.visit_materials(visit);
+
+        if !Arc::ptr_eq(&self.left, &self.right) {
+            self.right.visit_materials(visit);
+        }
+    }
 }
\ No newline at end of file
@@ -10,6 +10,7 @@ use crate::material::Material;
 use crate::ray::Ray;
 use crate::texture::solid_color::SolidColor;
 use crate::texture::Texture;
+use crate::sampler::SampleStream;
 
 pub struct ConstantMedium {
     pub boundary: Arc<dyn Hittable>,
@@ -36,10 +37,10 @@ impl ConstantMedium {
 }
 
 impl Hittable for ConstantMedium {
-    fn hit(&self, ray: Ray, ray_t: Interval) -> Option<HitRecord> {
-        let mut rec1 = self.boundary.hit(ray, interval::UNIVERSE)?;
+    fn hit(&self, ray: Ray, ray_t: Interval, sampler: &mut SampleStream) -> Option<HitRecord> {
+        let mut rec1 = self.boundary.hit(ray, interval::UNIVERSE, sampler)?;
 
-        let mut rec2 = self.boundary.hit(ray, Interval { min: rec1.t + Self::HIT_EPSILON, max: f32::INFINITY })?;
+        let mut rec2 = self.boundary.hit(ray, Interval { min: rec1.t + Self::HIT_EPSILON, max: f32::INFINITY }, sampler)?;
 
         rec1.t = rec1.t.max(ray_t.min);
         rec2.t = rec2.t.min(ray_t.max);
@@ -52,7 +53,7 @@ impl Hittable for ConstantMedium {
 
         let ray_length = ray.direction.magnitude();
         let dist_inside_boundary = (rec2.t - rec1.t) * ray_length;
-        let hit_distance = self.neg_inv_density * rand::random_range(0.0f32..1.0).ln();
+        let hit_distance = self.neg_inv_density * sampler.get_1d().ln();
 
         if hit_distance > dist_inside_boundary {
             return None;
@@ -68,6 +69,7 @@ impl Hittable for ConstantMedium {
                 p: ray.at(t),
                 t,
                 uv: Vec2::default(),
+                object_id: 0,
             }
         )
     }
@@ -75,4 +77,8 @@ impl Hittable for ConstantMedium {
     fn bounding_box(&self) -> &AABB {
         self.boundary.bounding_box()
     }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        visit(&self.phase_function);
+    }
 }
\ No newline at end of file
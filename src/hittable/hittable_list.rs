@@ -1,13 +1,18 @@
 use std::sync::Arc;
+use glm::Vec3;
 use crate::aabb::AABB;
 use crate::hittable::{HitRecord, Hittable};
 use crate::interval::Interval;
+use crate::material::Material;
 use crate::ray::Ray;
+use crate::sampler::SampleStream;
 
 #[derive(Default)]
 pub struct HittableList {
     pub objects: Vec<Arc<dyn Hittable>>,
     aabb: AABB,
+    // the pieces of a single object, like a box's faces, whose hits are numbered as that object by whatever holds it
+    parts: bool,
 }
 
 impl HittableList {
@@ -15,24 +20,166 @@ impl HittableList {
         self.aabb = self.aabb.enclosing(object.bounding_box());
         self.objects.push(object);
     }
+
+    pub fn parts() -> Self {
+        Self { parts: true, ..Self::default() }
+    }
+
+    // wraps the objects so they keep their ids once rearranged into a BVH
+    pub(super) fn into_numbered_objects(self) -> Vec<Arc<dyn Hittable>> {
+        if self.parts {
+            return self.objects;
+        }
+
+        let single = self.objects.len() == 1;
+
+        self.objects.into_iter()
+            .enumerate()
+            .map(|(index, object)| Arc::new(NumberedObject { id: index as u32 + 1, single, object }) as Arc<dyn Hittable>)
+            .collect()
+    }
 }
 
 impl Hittable for HittableList {
-    fn hit(&self, ray: Ray, ray_t: Interval) -> Option<HitRecord> {
+    fn hit(&self, ray: Ray, ray_t: Interval, sampler: &mut SampleStream) -> Option<HitRecord> {
         let mut hit_rec = None;
         let mut curr_closest = ray_t.max;
 
-        for object in &self.objects {
-            if let Some(rec) = object.hit(ray, Interval { min: ray_t.min, max: curr_closest }) {
+        for (index, object) in self.objects.iter().enumerate() {
+            if let Some(rec) = object.hit(ray, Interval { min: ray_t.min, max: curr_closest }, sampler) {
                 curr_closest = rec.t;
-                hit_rec = Some(rec);
+                hit_rec = Some((index, rec));
             }
         }
 
-        hit_rec
+        hit_rec.map(|(index, rec)| if self.parts {
+            rec
+        } else {
+            HitRecord { object_id: object_id(index as u32 + 1, self.objects.len() == 1, rec.object_id), ..rec }
+        })
     }
 
     fn bounding_box(&self) -> &AABB {
         &self.aabb
     }
-}
\ No newline at end of file
+
+    fn pdf_value(&self, origin: Vec3, direction: Vec3, time: f32) -> f32 {
+        let weight = 1.0 / self.objects.len() as f32;
+
+        self.objects.iter()
+            .map(|object| weight * object.pdf_value(origin, direction, time))
+            .sum()
+    }
+
+    fn random(&self, origin: Vec3, time: f32, sampler: &mut SampleStream) -> Vec3 {
+        let index = (sampler.get_1d() * self.objects.len() as f32) as usize;
+
+        self.objects[index.min(self.objects.len() - 1)].random(origin, time, sampler)
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        self.objects.iter().for_each(|object| object.visit_materials(visit));
+    }
+}
+
+// hits are numbered by the outermost list or BVH that has more than one object, while one of a single object only
+// numbers hits nothing inside it has, so a list wrapping a single BVH leaves its objects' ids alone; ids start at 1,
+// 0 is left for hitting nothing
+fn object_id(id: u32, single: bool, inner_id: u32) -> u32 {
+    if single && inner_id != 0 { inner_id } else { id }
+}
+
+struct NumberedObject {
+    id: u32,
+    // the only object of its list
+    single: bool,
+    object: Arc<dyn Hittable>,
+}
+
+impl Hittable for NumberedObject {
+    fn hit(&self, ray: Ray, ray_t: Interval, sampler: &mut SampleStream) -> Option<HitRecord> {
+        self.object.hit(ray, ray_t, sampler).map(|rec| HitRecord { object_id: object_id(self.id, self.single, rec.object_id), ..rec })
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        self.object.bounding_box()
+    }
+
+    fn pdf_value(&self, origin: Vec3, direction: Vec3, time: f32) -> f32 {
+        self.object.pdf_value(origin, direction, time)
+    }
+
+    fn random(&self, origin: Vec3, time: f32, sampler: &mut SampleStream) -> Vec3 {
+        self.object.random(origin, time, sampler)
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        self.object.visit_materials(visit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::hittable::bvh::BVHNode;
+    use crate::hittable::rect_prism::RectangularPrism;
+    use crate::hittable::sphere::Sphere;
+    use crate::material::lambertian::Lambertian;
+    use crate::sampler::independent::IndependentSampler;
+    use super::*;
+
+    fn list(objects: impl IntoIterator<Item = Arc<dyn Hittable>>) -> HittableList {
+        let mut list = HittableList::default();
+        objects.into_iter().for_each(|object| list.add(object));
+        list
+    }
+
+    fn sphere(x: f32) -> Arc<dyn Hittable> {
+        Arc::new(Sphere::new(Vec3::new(x, 0.0, 0.0), 0.5, Arc::new(Lambertian::from_albedo(Color::repeat(0.5)))))
+    }
+
+    // the id of whatever a ray from the origin hits first in that direction
+    fn object_id(world: &dyn Hittable, origin: Vec3, direction: Vec3) -> Option<u32> {
+        let sampler = IndependentSampler { seed: 0 };
+        let ray = Ray { origin, direction, time: 0.0 };
+
+        world.hit(ray, Interval { min: 0.001, max: f32::INFINITY }, &mut SampleStream::new(&sampler, 0, 0, 0))
+            .map(|rec| rec.object_id)
+    }
+
+    #[test]
+    fn numbers_objects_from_one() {
+        let world = list([sphere(0.0), sphere(2.0)]);
+
+        assert_eq!(object_id(&world, Vec3::new(0.0, 0.0, 5.0), -Vec3::z()), Some(1));
+        assert_eq!(object_id(&world, Vec3::new(2.0, 0.0, 5.0), -Vec3::z()), Some(2));
+    }
+
+    #[test]
+    fn a_lone_box_has_one_id_on_every_face() {
+        let material = Arc::new(Lambertian::from_albedo(Color::repeat(0.5)));
+        let world = list([Arc::new(RectangularPrism::from_opposite_vertices(Vec3::repeat(-1.0), Vec3::repeat(1.0), material)) as _]);
+
+        for axis in [Vec3::x(), Vec3::y(), Vec3::z()] {
+            assert_eq!(object_id(&world, 5.0 * axis, -axis), Some(1));
+            assert_eq!(object_id(&world, -5.0 * axis, axis), Some(1));
+        }
+    }
+
+    #[test]
+    fn a_list_of_a_single_bvh_keeps_its_ids() {
+        let world = list([Arc::new(BVHNode::from(list([sphere(0.0), sphere(2.0)]))) as _]);
+
+        assert_eq!(object_id(&world, Vec3::new(0.0, 0.0, 5.0), -Vec3::z()), Some(1));
+        assert_eq!(object_id(&world, Vec3::new(2.0, 0.0, 5.0), -Vec3::z()), Some(2));
+    }
+
+    #[test]
+    fn outer_lists_renumber_inner_ones() {
+        let world = list([sphere(-2.0), Arc::new(list([sphere(0.0), sphere(2.0)])) as _]);
+
+        assert_eq!(object_id(&world, Vec3::new(-2.0, 0.0, 5.0), -Vec3::z()), Some(1));
+        assert_eq!(object_id(&world, Vec3::new(0.0, 0.0, 5.0), -Vec3::z()), Some(2));
+        assert_eq!(object_id(&world, Vec3::new(2.0, 0.0, 5.0), -Vec3::z()), Some(2));
+    }
+}
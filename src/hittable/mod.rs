@@ -4,6 +4,7 @@ use crate::interval::Interval;
 use crate::material::Material;
 use crate::ray::Ray;
 use crate::aabb::AABB;
+use crate::sampler::SampleStream;
 
 pub mod hittable_list;
 pub mod sphere;
@@ -12,11 +13,27 @@ pub mod quad;
 pub mod transform;
 pub mod constant_medium;
 pub mod rect_prism;
+pub mod triangle;
+pub mod triangle_mesh;
 
 pub trait Hittable: Send + Sync {
-    fn hit(&self, ray: Ray, ray_t: Interval) -> Option<HitRecord>;
+    fn hit(&self, ray: Ray, ray_t: Interval, sampler: &mut SampleStream) -> Option<HitRecord>;
 
     fn bounding_box(&self) -> &AABB;
+
+    #[expect(unused_variables, reason = "trait method")]
+    fn pdf_value(&self, origin: Vec3, direction: Vec3, time: f32) -> f32 {
+        0.0
+    }
+
+    #[expect(unused_variables, reason = "trait method")]
+    fn random(&self, origin: Vec3, time: f32, sampler: &mut SampleStream) -> Vec3 {
+        Vec3::new(1.0, 0.0, 0.0)
+    }
+
+    // every material the object can hit with, in a fixed order; containers visit their objects' materials
+    #[expect(unused_variables, reason = "trait method")]
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {}
 }
 
 #[derive(Clone)]
@@ -27,6 +44,8 @@ pub struct HitRecord {
     pub t: f32,
     pub uv: Vec2,
     pub front_face: bool,
+    // which of the scene's objects was hit, see hittable_list::object_id; 0 until a list or BVH numbers it
+    pub object_id: u32,
 }
 
 impl HitRecord {
@@ -38,6 +57,7 @@ impl HitRecord {
             p,
             front_face,
             t, uv,
+            object_id: 0,
             normal: if front_face {
                 outward_normal
             } else {
@@ -46,3 +66,10 @@ impl HitRecord {
         }
     }
 }
+
+// whether two edges from a shared corner span a surface, whatever the scale; false when either is zero, they're
+// parallel, or they aren't finite
+pub(crate) fn edges_span_surface(u: Vec3, v: Vec3) -> bool {
+    let n = u.cross(&v);
+    n.iter().all(|c| c.is_finite()) && n.magnitude_squared() > f32::EPSILON * u.magnitude_squared() * v.magnitude_squared()
+}
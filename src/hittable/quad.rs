@@ -1,12 +1,13 @@
 use std::sync::Arc;
 use glm::{Vec2, Vec3};
 use crate::aabb::AABB;
-use crate::hittable::{HitRecord, Hittable};
+use crate::hittable::{HitRecord, Hittable, edges_span_surface};
 use crate::hittable::hittable_list::HittableList;
 use crate::interval;
 use crate::interval::Interval;
 use crate::material::Material;
 use crate::ray::Ray;
+use crate::sampler::SampleStream;
 
 // TODO: flat primitive struct
 
@@ -17,6 +18,7 @@ pub struct Quad {
     w: Vec3,
     normal: Vec3,
     d: f32,
+    area: f32,
     material: Arc<dyn Material>,
     aabb: AABB,
 }
@@ -25,10 +27,7 @@ impl Quad {
     pub const PARALLEL_THRESHOLD: f32 = 1e-8;
 
     pub fn new(q: Vec3, u: Vec3, v: Vec3, material: Arc<dyn Material>) -> Self {
-        assert!(
-            u.magnitude_squared() >= f32::EPSILON && v.magnitude_squared() >= f32::EPSILON,
-            "Quad edges must be non-zero vectors"
-        );
+        assert!(edges_span_surface(u, v), "Quad edges must be non-zero and not parallel");
 
         let diag1 = AABB::from_extrema(q, q + u + v);
         let diag2 = AABB::from_extrema(q + u, q + v);
@@ -38,10 +37,11 @@ impl Quad {
         let normal = n.normalize();
         let d = normal.dot(&q);
         let w = n / n.magnitude_squared();
+        let area = n.magnitude();
 
         Self {
             q, u, v, w,
-            normal, d,
+            normal, d, area,
             material,
             aabb,
         }
@@ -50,10 +50,9 @@ impl Quad {
     fn is_interior(a: f32, b: f32) -> bool {
         interval::UNIT.contains(a) && interval::UNIT.contains(b)
     }
-}
 
-impl Hittable for Quad {
-    fn hit(&self, ray: Ray, ray_t: Interval) -> Option<HitRecord> {
+    // returns the ray parameter and the hit's coordinates along u & v
+    fn intersect(&self, ray: Ray, ray_t: Interval) -> Option<(f32, Vec2)> {
         let denominator = self.normal.dot(&ray.direction);
 
         if denominator.abs() < Self::PARALLEL_THRESHOLD {
@@ -70,12 +69,14 @@ impl Hittable for Quad {
         let planar_hit_point_vector = intersection - self.q;
         let alpha = self.w.dot(&planar_hit_point_vector.cross(&self.v));
         let beta = self.w.dot(&self.u.cross(&planar_hit_point_vector));
-        
-        let uv = Vec2::new(alpha, beta);
-        
-        if !Self::is_interior(alpha, beta) {
-            return None;
-        }
+
+        Self::is_interior(alpha, beta).then_some((t, Vec2::new(alpha, beta)))
+    }
+}
+
+impl Hittable for Quad {
+    fn hit(&self, ray: Ray, ray_t: Interval, _sampler: &mut SampleStream) -> Option<HitRecord> {
+        let (t, uv) = self.intersect(ray, ray_t)?;
 
         Some(HitRecord::new_with_outward_normal(
             ray.at(t),
@@ -90,6 +91,28 @@ impl Hittable for Quad {
     fn bounding_box(&self) -> &AABB {
         &self.aabb
     }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        visit(&self.material);
+    }
+
+    fn pdf_value(&self, origin: Vec3, direction: Vec3, time: f32) -> f32 {
+        let Some((t, _)) = self.intersect(Ray { origin, direction, time }, Interval { min: 0.001, max: f32::INFINITY }) else {
+            return 0.0;
+        };
+
+        let distance_squared = t * t * direction.magnitude_squared();
+        let cosine = (direction.dot(&self.normal) / direction.magnitude()).abs();
+
+        distance_squared / (cosine * self.area)
+    }
+
+    fn random(&self, origin: Vec3, _time: f32, sampler: &mut SampleStream) -> Vec3 {
+        let u = sampler.get_2d();
+        let p = self.q + u.x * self.u + u.y * self.v;
+
+        p - origin
+    }
 }
 
 pub fn make_box_from_opposite_vertices(a: Vec3, b: Vec3, material: Arc<dyn Material>) -> [Quad; 6] {
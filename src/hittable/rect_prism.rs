@@ -7,6 +7,7 @@ use crate::hittable::quad::{make_box_from_opposite_vertices, Quad};
 use crate::interval::Interval;
 use crate::material::Material;
 use crate::ray::Ray;
+use crate::sampler::SampleStream;
 
 pub struct RectangularPrism {
     faces: HittableList,
@@ -14,7 +15,7 @@ pub struct RectangularPrism {
 
 impl RectangularPrism {
     pub fn from_quads(quads: [Quad; 6]) -> Self {
-        let mut faces = HittableList::default();
+        let mut faces = HittableList::parts();
         quads.into_iter().for_each(|face| faces.add(Arc::new(face)));
         Self { faces }
     }
@@ -26,11 +27,23 @@ impl RectangularPrism {
 
 
 impl Hittable for RectangularPrism {
-    fn hit(&self, ray: Ray, ray_t: Interval) -> Option<HitRecord> {
-        self.bounding_box().hit(ray, ray_t).then(|| self.faces.hit(ray, ray_t)).flatten()
+    fn hit(&self, ray: Ray, ray_t: Interval, sampler: &mut SampleStream) -> Option<HitRecord> {
+        self.bounding_box().hit(ray, ray_t).then(|| self.faces.hit(ray, ray_t, sampler)).flatten()
     }
 
     fn bounding_box(&self) -> &AABB {
         self.faces.bounding_box()
     }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        self.faces.visit_materials(visit);
+    }
+
+    fn pdf_value(&self, origin: Vec3, direction: Vec3, time: f32) -> f32 {
+        self.faces.pdf_value(origin, direction, time)
+    }
+
+    fn random(&self, origin: Vec3, time: f32, sampler: &mut SampleStream) -> Vec3 {
+        self.faces.random(origin, time, sampler)
+    }
 }
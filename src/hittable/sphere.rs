@@ -5,9 +5,14 @@ use crate::hittable::{HitRecord, Hittable};
 use crate::interval::Interval;
 use crate::material::Material;
 use crate::ray::Ray;
+use crate::sampler::SampleStream;
+use crate::util::onb::Onb;
+use crate::util::warp::{uniform_cone_to_sphere, uniform_sphere};
 
 pub struct Sphere {
+    // at time 0, the sphere moves in a straight line by `motion` until time 1 and stays put outside of that
     center: Vec3,
+    motion: Vec3,
     radius: f32,
     material: Arc<dyn Material>,
     aabb: AABB,
@@ -15,21 +20,31 @@ pub struct Sphere {
 
 impl Sphere {
     pub fn new(center: Vec3, radius: f32, material: Arc<dyn Material>) -> Self {
+        Self::moving(center, center, radius, material)
+    }
+
+    // centered at center0 at time 0 and at center1 at time 1
+    pub fn moving(center0: Vec3, center1: Vec3, radius: f32, material: Arc<dyn Material>) -> Self {
         assert!(radius > 0.0, "Sphere radius must be positive");
         
         let r_vec = Vec3::from_element(radius);
 
+        let aabb0 = AABB::from_extrema(center0 - r_vec, center0 + r_vec);
+        let aabb1 = AABB::from_extrema(center1 - r_vec, center1 + r_vec);
+
         Self {
-            center,
+            center: center0,
+            motion: center1 - center0,
             radius,
             material,
-            aabb: AABB::from_extrema(
-                center - r_vec,
-                center + r_vec,
-            ),
+            aabb: aabb0.enclosing(&aabb1),
         }
     }
 
+    fn center_at(&self, time: f32) -> Vec3 {
+        self.center + time.clamp(0.0, 1.0) * self.motion
+    }
+
     pub fn get_uv(p: Vec3) -> Vec2 {
         use std::f32::consts::{ PI, TAU };
 
@@ -38,11 +53,10 @@ impl Sphere {
 
         Vec2::new( phi / TAU, theta / PI )
     }
-}
 
-impl Hittable for Sphere {
-    fn hit(&self, ray: Ray, ray_t: Interval) -> Option<HitRecord> {
-        let oc = self.center - ray.origin;
+    // returns the ray parameter of the nearest intersection within ray_t
+    fn intersect(&self, ray: Ray, ray_t: Interval) -> Option<f32> {
+        let oc = self.center_at(ray.time) - ray.origin;
         let a = ray.direction.magnitude_squared();
         let h = ray.direction.dot(&oc);
         let c = oc.magnitude_squared() - self.radius * self.radius;
@@ -64,9 +78,17 @@ impl Hittable for Sphere {
             }
         }
 
+        Some(root)
+    }
+}
+
+impl Hittable for Sphere {
+    fn hit(&self, ray: Ray, ray_t: Interval, _sampler: &mut SampleStream) -> Option<HitRecord> {
+        let root = self.intersect(ray, ray_t)?;
+
         let p = ray.at(root);
 
-        let outward_normal = (p - self.center) / self.radius;
+        let outward_normal = (p - self.center_at(ray.time)) / self.radius;
         
         Some(
             HitRecord::new_with_outward_normal(
@@ -83,4 +105,39 @@ impl Hittable for Sphere {
     fn bounding_box(&self) -> &AABB {
         &self.aabb
     }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        visit(&self.material);
+    }
+
+    fn pdf_value(&self, origin: Vec3, direction: Vec3, time: f32) -> f32 {
+        use std::f32::consts::TAU;
+
+        if self.intersect(Ray { origin, direction, time }, Interval { min: 0.001, max: f32::INFINITY }).is_none() {
+            return 0.0;
+        }
+
+        let distance_squared = (self.center_at(time) - origin).magnitude_squared();
+
+        // from inside the sphere, every direction hits it
+        if distance_squared <= self.radius * self.radius {
+            return 1.0 / (2.0 * TAU);
+        }
+
+        let cos_theta_max = (1.0 - self.radius * self.radius / distance_squared).sqrt();
+        let solid_angle = TAU * (1.0 - cos_theta_max);
+
+        1.0 / solid_angle
+    }
+
+    fn random(&self, origin: Vec3, time: f32, sampler: &mut SampleStream) -> Vec3 {
+        let direction = self.center_at(time) - origin;
+        let distance_squared = direction.magnitude_squared();
+
+        if distance_squared <= self.radius * self.radius {
+            return uniform_sphere(sampler.get_2d());
+        }
+
+        Onb::new(direction).transform(uniform_cone_to_sphere(self.radius, distance_squared, sampler.get_2d()))
+    }
 }
\ No newline at end of file
@@ -4,65 +4,142 @@ use na::Rotation3;
 use crate::aabb::AABB;
 use crate::hittable::{HitRecord, Hittable};
 use crate::interval::Interval;
+use crate::material::Material;
 use crate::ray::Ray;
+use crate::sampler::SampleStream;
+
+// where the object is at a point in time, with angles in degrees
+#[derive(Copy, Clone, Debug)]
+pub struct Keyframe {
+    pub time: f32,
+    pub translation: Vec3,
+    pub angles: Vec3,
+}
 
 pub struct Transform {
     object: Arc<dyn Hittable>,
+    // sorted by time. the object holds still before the first and after the last, in between it moves in a straight
+    // line and turns at a constant rate, the shortest way around
+    poses: Vec<Pose>,
+    aabb: AABB,
+}
+
+#[derive(Copy, Clone)]
+struct Pose {
+    time: f32,
     translation: Vec3,
     rotation: Rotation3<f32>,
-    aabb: AABB,
 }
 
 impl Transform {
+    // the angle the object turns between the poses its bounding box is computed at
+    const MAX_STEP_ANGLE: f32 = std::f32::consts::PI / 16.0;
+
     pub fn new(object: Arc<dyn Hittable>, translation: Vec3, angles: Vec3) -> Self {
+        Self::keyframed(object, &[Keyframe { time: 0.0, translation, angles }])
+    }
+
+    pub fn keyframed(object: Arc<dyn Hittable>, keyframes: &[Keyframe]) -> Self {
+        assert!(!keyframes.is_empty(), "Transform needs at least one keyframe");
+
+        let mut poses: Vec<Pose> = keyframes.iter()
+            .map(|keyframe| Pose {
+                time: keyframe.time,
+                translation: keyframe.translation,
+                rotation: Rotation3::new(keyframe.angles.map(f32::to_radians)),
+            })
+            .collect();
+
+        poses.sort_by(|a, b| a.time.total_cmp(&b.time));
+
         let aabb = object.bounding_box();
 
-        let mut max = Vec3::from_element(f32::INFINITY);
-        let mut min = Vec3::from_element(f32::NEG_INFINITY);
+        let corners: Vec<Vec3> = (0..8)
+            .map(|corner| Vec3::from_fn(|i, _| if corner >> i & 1 == 1 { aabb[i].max } else { aabb[i].min }))
+            .collect();
 
-        let rotation = Rotation3::new(angles.map(f32::to_radians));
+        // between poses the corners move along arcs, which stay within this much of the straight lines between the
+        // poses the box is computed at
+        let reach = corners.iter().map(|corner| corner.magnitude()).fold(0.0, f32::max);
 
-        for i in 0..2 {
-            for j in 0..2 {
-                for k in 0..2 {
-                    let loc = Vec3::new(i as f32, j as f32, k as f32);
+        let mut aabb = poses[0].aabb(&corners);
 
-                    let vec = rotation * Vec3::from_fn(|i, _|
-                        loc[i] * aabb[i].max + (1.0 - loc[i]) * aabb[i].min
-                    );
+        for pair in poses.windows(2) {
+            let angle = pair[0].rotation.rotation_to(&pair[1].rotation).angle();
+            let steps = (angle / Self::MAX_STEP_ANGLE).ceil().max(1.0) as u32;
+            let padding = reach * (1.0 - (angle / steps as f32 / 2.0).cos());
 
-                    for n in 0..3 {
-                        min[n] = min[n].min(vec[n]);
-                        max[n] = max[n].max(vec[n]);
-                    }
-                }
+            for step in 0..=steps {
+                let pose_aabb = Pose::between(&pair[0], &pair[1], step as f32 / steps as f32).aabb(&corners);
+
+                aabb = aabb.enclosing(&if padding > 0.0 {
+                    AABB::new(pose_aabb.x.expanded(2.0 * padding), pose_aabb.y.expanded(2.0 * padding), pose_aabb.z.expanded(2.0 * padding))
+                } else {
+                    pose_aabb
+                });
             }
         }
 
-        Self {
-            object,
-            translation,
-            rotation,
-            aabb: AABB::from_extrema(min, max) + translation,
+        Self { object, poses, aabb }
+    }
+
+    fn pose_at(&self, time: f32) -> Pose {
+        let next = self.poses.partition_point(|pose| pose.time <= time);
+
+        match next {
+            0 => self.poses[0],
+            n if n == self.poses.len() => self.poses[n - 1],
+            n => {
+                let (from, to) = (&self.poses[n - 1], &self.poses[n]);
+                Pose::between(from, to, (time - from.time) / (to.time - from.time))
+            }
         }
     }
+}
+
+impl Pose {
+    fn between(from: &Pose, to: &Pose, t: f32) -> Pose {
+        Pose {
+            time: from.time + t * (to.time - from.time),
+            translation: from.translation.lerp(&to.translation, t),
+            rotation: from.rotation.slerp(&to.rotation, t),
+        }
+    }
+
+    // bounding box of the given object space corners
+    fn aabb(&self, corners: &[Vec3]) -> AABB {
+        let mut min = Vec3::from_element(f32::INFINITY);
+        let mut max = Vec3::from_element(f32::NEG_INFINITY);
 
+        for corner in corners {
+            // hits rotate rays into object space, so the object itself is rotated the opposite way
+            let vec = self.rotation.transpose() * corner;
 
+            for n in 0..3 {
+                min[n] = min[n].min(vec[n]);
+                max[n] = max[n].max(vec[n]);
+            }
+        }
+
+        AABB::from_extrema(min, max) + self.translation
+    }
 }
 
 impl Hittable for Transform {
-    fn hit(&self, ray: Ray, ray_t: Interval) -> Option<HitRecord> {
-        let offset_ray = self.rotation * Ray {
-            origin: ray.origin - self.translation,
+    fn hit(&self, ray: Ray, ray_t: Interval, sampler: &mut SampleStream) -> Option<HitRecord> {
+        let pose = self.pose_at(ray.time);
+
+        let offset_ray = pose.rotation * Ray {
+            origin: ray.origin - pose.translation,
             .. ray
         };
 
-        if let Some(rec) = self.object.hit(offset_ray, ray_t) {
-            let rot_transpose = self.rotation.transpose();
+        if let Some(rec) = self.object.hit(offset_ray, ray_t, sampler) {
+            let rot_transpose = pose.rotation.transpose();
 
             Some(
                 HitRecord {
-                    p: rot_transpose * rec.p + self.translation,
+                    p: rot_transpose * rec.p + pose.translation,
                     normal: rot_transpose * rec.normal,
                     .. rec
                 }
@@ -75,4 +152,18 @@ impl Hittable for Transform {
     fn bounding_box(&self) -> &AABB {
         &self.aabb
     }
-}
\ No newline at end of file
+
+    fn pdf_value(&self, origin: Vec3, direction: Vec3, time: f32) -> f32 {
+        let pose = self.pose_at(time);
+        self.object.pdf_value(pose.rotation * (origin - pose.translation), pose.rotation * direction, time)
+    }
+
+    fn random(&self, origin: Vec3, time: f32, sampler: &mut SampleStream) -> Vec3 {
+        let pose = self.pose_at(time);
+        pose.rotation.transpose() * self.object.random(pose.rotation * (origin - pose.translation), time, sampler)
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        self.object.visit_materials(visit);
+    }
+}
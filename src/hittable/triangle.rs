@@ -0,0 +1,283 @@
+use std::fmt;
+use std::sync::Arc;
+use glm::{Vec2, Vec3};
+use crate::aabb::AABB;
+use crate::hittable::{HitRecord, Hittable, edges_span_surface};
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::sampler::SampleStream;
+
+pub struct Triangle {
+    vertices: [Vec3; 3],
+    normals: Option<[Vec3; 3]>,
+    uvs: Option<[Vec2; 3]>,
+    normal: Vec3,
+    area: f32,
+    material: Arc<dyn Material>,
+    aabb: AABB,
+}
+
+// two of the vertices coincide, or all three lie on a line
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DegenerateTriangle;
+
+impl Triangle {
+    // the sine of the angle between a ray and the triangle's plane below which the ray is taken to miss it
+    pub const PARALLEL_THRESHOLD: f32 = 1e-8;
+
+    pub fn new(vertices: [Vec3; 3], material: Arc<dyn Material>) -> Result<Self, DegenerateTriangle> {
+        Self::with_attributes(vertices, None, None, material)
+    }
+
+    pub fn with_attributes(
+        vertices: [Vec3; 3],
+        normals: Option<[Vec3; 3]>,
+        uvs: Option<[Vec2; 3]>,
+        material: Arc<dyn Material>,
+    ) -> Result<Self, DegenerateTriangle> {
+        if is_degenerate(vertices) {
+            return Err(DegenerateTriangle);
+        }
+
+        let n = geometric_normal(vertices);
+
+        Ok(Self {
+            vertices,
+            normals,
+            uvs,
+            normal: n.normalize(),
+            area: n.magnitude() / 2.0,
+            material,
+            aabb: bounding_box(vertices),
+        })
+    }
+}
+
+impl fmt::Display for DegenerateTriangle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "triangle vertices are collinear")
+    }
+}
+
+impl std::error::Error for DegenerateTriangle {}
+
+impl Hittable for Triangle {
+    fn hit(&self, ray: Ray, ray_t: Interval, _sampler: &mut SampleStream) -> Option<HitRecord> {
+        let (t, barycentric) = intersect(self.vertices, ray, ray_t)?;
+
+        Some(make_hit_record(
+            ray,
+            t,
+            barycentric,
+            self.normal,
+            self.normals,
+            self.uvs,
+            self.material.clone(),
+        ))
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.aabb
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        visit(&self.material);
+    }
+
+    fn pdf_value(&self, origin: Vec3, direction: Vec3, time: f32) -> f32 {
+        pdf_value(self.vertices, self.normal, self.area, Ray { origin, direction, time })
+    }
+
+    fn random(&self, origin: Vec3, _time: f32, sampler: &mut SampleStream) -> Vec3 {
+        random_point(self.vertices, sampler) - origin
+    }
+}
+
+pub(crate) fn geometric_normal([a, b, c]: [Vec3; 3]) -> Vec3 {
+    (b - a).cross(&(c - a))
+}
+
+pub(crate) fn is_degenerate([a, b, c]: [Vec3; 3]) -> bool {
+    !edges_span_surface(b - a, c - a)
+}
+
+// solid angle density of sampling the triangle uniformly by area, seen along the ray
+pub(crate) fn pdf_value(vertices: [Vec3; 3], normal: Vec3, area: f32, ray: Ray) -> f32 {
+    let Some((t, _)) = intersect(vertices, ray, Interval { min: 0.001, max: f32::INFINITY }) else {
+        return 0.0;
+    };
+
+    let distance_squared = t * t * ray.direction.magnitude_squared();
+    let cosine = (ray.direction.dot(&normal) / ray.direction.magnitude()).abs();
+
+    distance_squared / (cosine * area)
+}
+
+pub(crate) fn random_point([a, b, c]: [Vec3; 3], sampler: &mut SampleStream) -> Vec3 {
+    let mut u = sampler.get_2d();
+
+    // reflect samples from the other half of the parallelogram back into the triangle
+    if u.x + u.y > 1.0 {
+        u = Vec2::from_element(1.0) - u;
+    }
+
+    a + u.x * (b - a) + u.y * (c - a)
+}
+
+pub(crate) fn bounding_box([a, b, c]: [Vec3; 3]) -> AABB {
+    let min = Vec3::from_fn(|i, _| a[i].min(b[i]).min(c[i]));
+    let max = Vec3::from_fn(|i, _| a[i].max(b[i]).max(c[i]));
+
+    AABB::from_extrema(min, max)
+}
+
+// Möller–Trumbore; returns the ray parameter and the barycentric weights of the 2nd & 3rd vertices
+pub(crate) fn intersect([a, b, c]: [Vec3; 3], ray: Ray, ray_t: Interval) -> Option<(f32, Vec2)> {
+    let edge1 = b - a;
+    let edge2 = c - a;
+
+    let p = ray.direction.cross(&edge2);
+    let determinant = edge1.dot(&p);
+
+    // relative to the lengths involved, so small triangles aren't missed
+    let scale_squared = ray.direction.magnitude_squared() * edge1.magnitude_squared() * edge2.magnitude_squared();
+
+    if determinant * determinant < Triangle::PARALLEL_THRESHOLD * Triangle::PARALLEL_THRESHOLD * scale_squared {
+        return None;
+    }
+
+    let inv_determinant = determinant.recip();
+
+    let s = ray.origin - a;
+    let beta = s.dot(&p) * inv_determinant;
+
+    if !(0.0..=1.0).contains(&beta) {
+        return None;
+    }
+
+    let q = s.cross(&edge1);
+    let gamma = ray.direction.dot(&q) * inv_determinant;
+
+    if gamma < 0.0 || beta + gamma > 1.0 {
+        return None;
+    }
+
+    let t = edge2.dot(&q) * inv_determinant;
+
+    ray_t.contains(t).then_some((t, Vec2::new(beta, gamma)))
+}
+
+pub(crate) fn make_hit_record(
+    ray: Ray,
+    t: f32,
+    barycentric: Vec2,
+    normal: Vec3,
+    normals: Option<[Vec3; 3]>,
+    uvs: Option<[Vec2; 3]>,
+    material: Arc<dyn Material>,
+) -> HitRecord {
+    let uv = match uvs {
+        Some(uvs) => interpolate(uvs, barycentric),
+        None => barycentric,
+    };
+
+    let mut rec = HitRecord::new_with_outward_normal(ray.at(t), t, uv, ray, normal, material);
+
+    // front face is decided by the geometric normal, so smooth shading can't flip which side was hit
+    if let Some(normals) = normals {
+        let shading_normal = interpolate(normals, barycentric).normalize();
+
+        rec.normal = if rec.front_face { shading_normal } else { -shading_normal };
+    }
+
+    rec
+}
+
+fn interpolate<T>([a, b, c]: [T; 3], barycentric: Vec2) -> T
+where
+    T: std::ops::Mul<f32, Output = T> + std::ops::Add<Output = T>,
+{
+    a * (1.0 - barycentric.x - barycentric.y) + b * barycentric.x + c * barycentric.y
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::material::lambertian::Lambertian;
+    use crate::sampler::independent::IndependentSampler;
+    use super::*;
+
+    const VERTICES: [Vec3; 3] = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+
+    fn material() -> Arc<dyn Material> {
+        Arc::new(Lambertian::from_albedo(Color::repeat(0.5)))
+    }
+
+    fn hit(triangle: &Triangle, origin: Vec3, direction: Vec3) -> Option<HitRecord> {
+        let sampler = IndependentSampler { seed: 0 };
+        let ray = Ray { origin, direction, time: 0.0 };
+
+        triangle.hit(ray, Interval { min: 0.001, max: f32::INFINITY }, &mut SampleStream::new(&sampler, 0, 0, 0))
+    }
+
+    #[test]
+    fn hits_inside_and_misses_outside() {
+        let triangle = Triangle::new(VERTICES, material()).expect("triangle isn't degenerate");
+
+        let rec = hit(&triangle, Vec3::new(0.25, 0.5, 2.0), -Vec3::z()).expect("ray passes through the triangle");
+        assert_eq!(rec.t, 2.0);
+        assert_eq!(rec.p, Vec3::new(0.25, 0.5, 0.0));
+        assert_eq!(rec.uv, Vec2::new(0.25, 0.5));
+        assert!(rec.front_face);
+        assert_eq!(rec.normal, Vec3::z());
+
+        assert!(hit(&triangle, Vec3::new(0.75, 0.75, 2.0), -Vec3::z()).is_none());
+        assert!(hit(&triangle, Vec3::new(-0.25, 0.5, 2.0), -Vec3::z()).is_none());
+        // parallel to the triangle's plane
+        assert!(hit(&triangle, Vec3::new(-1.0, 0.25, 0.0), Vec3::x()).is_none());
+        // behind the ray's origin
+        assert!(hit(&triangle, Vec3::new(0.25, 0.25, -2.0), -Vec3::z()).is_none());
+    }
+
+    #[test]
+    fn hits_edges_and_corners() {
+        let triangle = Triangle::new(VERTICES, material()).expect("triangle isn't degenerate");
+
+        for point in [Vec3::new(0.5, 0.0, 0.0), Vec3::new(0.0, 0.5, 0.0), Vec3::new(0.5, 0.5, 0.0), Vec3::new(1.0, 0.0, 0.0)] {
+            let rec = hit(&triangle, point + Vec3::z(), -Vec3::z());
+            assert!(rec.is_some_and(|rec| rec.p == point), "{point} should be hit");
+        }
+    }
+
+    #[test]
+    fn back_face_hits_flip_the_smooth_normal() {
+        let normals = [Vec3::z(), Vec3::new(1.0, 0.0, 1.0).normalize(), Vec3::new(0.0, 1.0, 1.0).normalize()];
+        let uvs = [Vec2::new(0.5, 0.5), Vec2::new(1.0, 0.5), Vec2::new(0.5, 1.0)];
+        let triangle = Triangle::with_attributes(VERTICES, Some(normals), Some(uvs), material())
+            .expect("triangle isn't degenerate");
+
+        let shading_normal = (0.5 * normals[0] + 0.25 * normals[1] + 0.25 * normals[2]).normalize();
+
+        let front = hit(&triangle, Vec3::new(0.25, 0.25, 1.0), -Vec3::z()).expect("ray passes through the triangle");
+        assert!(front.front_face);
+        assert!((front.normal - shading_normal).magnitude() < 1e-6);
+        assert_eq!(front.uv, Vec2::new(0.625, 0.625));
+
+        let back = hit(&triangle, Vec3::new(0.25, 0.25, -1.0), Vec3::z()).expect("ray passes through the triangle");
+        assert!(!back.front_face);
+        assert!((back.normal + shading_normal).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn rejects_degenerate_triangles_at_any_scale() {
+        let collinear = [Vec3::zeros(), Vec3::new(1.0, 1.0, 1.0), Vec3::new(3.0, 3.0, 3.0)];
+        assert!(Triangle::new(collinear, material()).is_err());
+        assert!(Triangle::new([Vec3::zeros(), Vec3::zeros(), Vec3::x()], material()).is_err());
+
+        for scale in [1e-4, 1.0, 1e4] {
+            let triangle = Triangle::new(VERTICES.map(|v| v * scale), material()).expect("triangle isn't degenerate");
+            assert!(hit(&triangle, Vec3::new(0.25 * scale, 0.25 * scale, 1.0), -Vec3::z()).is_some(), "scale {scale}");
+        }
+    }
+}
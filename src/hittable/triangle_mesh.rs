@@ -0,0 +1,299 @@
+use std::fmt;
+use std::sync::Arc;
+use glm::{Vec2, Vec3};
+use crate::aabb::AABB;
+use crate::hittable::{HitRecord, Hittable};
+use crate::hittable::bvh::BVHNode;
+use crate::hittable::triangle;
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::sampler::SampleStream;
+
+#[derive(Default, Clone, Debug)]
+pub struct VertexBuffers {
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    pub uvs: Vec<Vec2>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MeshFace {
+    pub positions: [usize; 3],
+    pub normals: Option<[usize; 3]>,
+    pub uvs: Option<[usize; 3]>,
+}
+
+impl MeshFace {
+    pub fn from_positions(positions: [usize; 3]) -> Self {
+        Self { positions, normals: None, uvs: None }
+    }
+
+    fn is_within(&self, buffers: &VertexBuffers) -> bool {
+        self.positions.iter().all(|&i| i < buffers.positions.len())
+            && self.normals.is_none_or(|n| n.iter().all(|&i| i < buffers.normals.len()))
+            && self.uvs.is_none_or(|uv| uv.iter().all(|&i| i < buffers.uvs.len()))
+    }
+}
+
+pub struct TriangleMesh {
+    bvh: BVHNode,
+    // to sample the mesh as a light, picking triangles by the running total of their areas
+    triangles: Vec<Arc<MeshTriangle>>,
+    cumulative_areas: Vec<f32>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MeshError {
+    // the face at this position in the list refers past the end of one of the vertex buffers
+    IndexOutOfBounds { face: usize },
+    // there are no faces, or all of them are degenerate
+    NoTriangles,
+}
+
+impl TriangleMesh {
+    pub fn new(buffers: Arc<VertexBuffers>, faces: &[MeshFace], material: Arc<dyn Material>) -> Self {
+        Self::try_new(buffers, faces, material).unwrap_or_else(|error| panic!("Invalid mesh: {error}"))
+    }
+
+    // degenerate faces are common in exported meshes, so they're skipped, as long as some triangle is left
+    pub fn try_new(buffers: Arc<VertexBuffers>, faces: &[MeshFace], material: Arc<dyn Material>) -> Result<Self, MeshError> {
+        let mut triangles = Vec::with_capacity(faces.len());
+
+        for (index, face) in faces.iter().enumerate() {
+            if !face.is_within(&buffers) {
+                return Err(MeshError::IndexOutOfBounds { face: index });
+            }
+
+            triangles.extend(MeshTriangle::new(buffers.clone(), face, material.clone()).map(Arc::new));
+        }
+
+        if triangles.is_empty() {
+            return Err(MeshError::NoTriangles);
+        }
+
+        let cumulative_areas = triangles.iter()
+            .scan(0.0, |total, tri| {
+                *total += tri.area;
+                Some(*total)
+            })
+            .collect();
+
+        let mut hittables = triangles.iter()
+            .map(|tri| tri.clone() as Arc<dyn Hittable>)
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            bvh: BVHNode::new(&mut hittables),
+            triangles,
+            cumulative_areas,
+        })
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    fn area(&self) -> f32 {
+        self.cumulative_areas.last().copied().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshError::IndexOutOfBounds { face } => write!(f, "face {face} refers to a vertex that doesn't exist"),
+            MeshError::NoTriangles => write!(f, "mesh has no non-degenerate triangles"),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+impl Hittable for TriangleMesh {
+    fn hit(&self, ray: Ray, ray_t: Interval, sampler: &mut SampleStream) -> Option<HitRecord> {
+        self.bvh.hit(ray, ray_t, sampler)
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        self.bvh.bounding_box()
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        self.bvh.visit_materials(visit);
+    }
+
+    // any triangle the direction passes through could have been sampled, so their densities add up. picked by area,
+    // each one's density over its own area becomes one over the whole mesh's. visits every triangle, so meshes used as
+    // lights are best kept small
+    fn pdf_value(&self, origin: Vec3, direction: Vec3, time: f32) -> f32 {
+        let ray = Ray { origin, direction, time };
+
+        if !self.bounding_box().hit(ray, Interval { min: 0.001, max: f32::INFINITY }) {
+            return 0.0;
+        }
+
+        let area = self.area();
+
+        self.triangles.iter()
+            .map(|tri| triangle::pdf_value(tri.vertices(), tri.normal, area, ray))
+            .sum()
+    }
+
+    fn random(&self, origin: Vec3, _time: f32, sampler: &mut SampleStream) -> Vec3 {
+        let target = sampler.get_1d() * self.area();
+        let index = self.cumulative_areas.partition_point(|&total| total <= target);
+
+        triangle::random_point(self.triangles[index.min(self.triangles.len() - 1)].vertices(), sampler) - origin
+    }
+}
+
+struct MeshTriangle {
+    buffers: Arc<VertexBuffers>,
+    face: MeshFace,
+    normal: Vec3,
+    area: f32,
+    material: Arc<dyn Material>,
+    aabb: AABB,
+}
+
+impl MeshTriangle {
+    // None for a degenerate face; its indices have to be in bounds
+    fn new(buffers: Arc<VertexBuffers>, face: &MeshFace, material: Arc<dyn Material>) -> Option<Self> {
+        let vertices = face.positions.map(|i| buffers.positions[i]);
+
+        if triangle::is_degenerate(vertices) {
+            return None;
+        }
+
+        let n = triangle::geometric_normal(vertices);
+
+        Some(Self {
+            face: *face,
+            normal: n.normalize(),
+            area: n.magnitude() / 2.0,
+            material,
+            aabb: triangle::bounding_box(vertices),
+            buffers,
+        })
+    }
+
+    fn vertices(&self) -> [Vec3; 3] {
+        self.face.positions.map(|i| self.buffers.positions[i])
+    }
+}
+
+impl Hittable for MeshTriangle {
+    fn hit(&self, ray: Ray, ray_t: Interval, _sampler: &mut SampleStream) -> Option<HitRecord> {
+        let (t, barycentric) = triangle::intersect(self.vertices(), ray, ray_t)?;
+
+        Some(triangle::make_hit_record(
+            ray,
+            t,
+            barycentric,
+            self.normal,
+            self.face.normals.map(|n| n.map(|i| self.buffers.normals[i])),
+            self.face.uvs.map(|uv| uv.map(|i| self.buffers.uvs[i])),
+            self.material.clone(),
+        ))
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.aabb
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        visit(&self.material);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+    use crate::color::Color;
+    use crate::material::lambertian::Lambertian;
+    use crate::sampler::sobol::SobolSampler;
+    use crate::util::warp::uniform_sphere;
+    use super::*;
+
+    fn material() -> Arc<dyn Material> {
+        Arc::new(Lambertian::from_albedo(Color::repeat(0.5)))
+    }
+
+    // a closed tetrahedron in front of the origin, so most directions that reach it pass through two faces
+    fn tetrahedron() -> TriangleMesh {
+        let buffers = VertexBuffers {
+            positions: vec![
+                Vec3::new(0.0, 0.0, 2.0),
+                Vec3::new(1.0, 0.0, 3.0),
+                Vec3::new(-0.5, 0.9, 3.0),
+                Vec3::new(-0.5, -0.9, 3.0),
+            ],
+            ..VertexBuffers::default()
+        };
+
+        let faces = [[0, 1, 2], [0, 2, 3], [0, 3, 1], [1, 3, 2]].map(MeshFace::from_positions);
+
+        TriangleMesh::new(Arc::new(buffers), &faces, material())
+    }
+
+    #[test]
+    fn rejects_faces_outside_the_vertex_buffers() {
+        let buffers = Arc::new(VertexBuffers {
+            positions: vec![Vec3::zeros(), Vec3::x(), Vec3::y()],
+            normals: vec![Vec3::z()],
+            ..VertexBuffers::default()
+        });
+
+        let faces = [MeshFace::from_positions([0, 1, 2]), MeshFace::from_positions([0, 1, 3])];
+        assert_eq!(TriangleMesh::try_new(buffers.clone(), &faces, material()).err(), Some(MeshError::IndexOutOfBounds { face: 1 }));
+
+        let faces = [MeshFace { normals: Some([0, 0, 1]), ..MeshFace::from_positions([0, 1, 2]) }];
+        assert_eq!(TriangleMesh::try_new(buffers.clone(), &faces, material()).err(), Some(MeshError::IndexOutOfBounds { face: 0 }));
+
+        let faces = [MeshFace { uvs: Some([0, 0, 0]), ..MeshFace::from_positions([0, 1, 2]) }];
+        assert_eq!(TriangleMesh::try_new(buffers, &faces, material()).err(), Some(MeshError::IndexOutOfBounds { face: 0 }));
+    }
+
+    #[test]
+    fn skips_degenerate_faces_but_needs_a_triangle() {
+        let buffers = Arc::new(VertexBuffers {
+            positions: vec![Vec3::zeros(), Vec3::x(), Vec3::y(), 2.0 * Vec3::x()],
+            ..VertexBuffers::default()
+        });
+
+        let faces = [[0, 1, 2], [0, 1, 3], [1, 1, 2]].map(MeshFace::from_positions);
+        let mesh = TriangleMesh::try_new(buffers.clone(), &faces, material()).expect("one face isn't degenerate");
+        assert_eq!(mesh.triangle_count(), 1);
+
+        let faces = [[0, 1, 3], [2, 2, 2]].map(MeshFace::from_positions);
+        assert_eq!(TriangleMesh::try_new(buffers.clone(), &faces, material()).err(), Some(MeshError::NoTriangles));
+        assert_eq!(TriangleMesh::try_new(buffers, &[], material()).err(), Some(MeshError::NoTriangles));
+    }
+
+    #[test]
+    fn pdf_integrates_to_one_over_the_sphere_of_directions() {
+        let mesh = tetrahedron();
+
+        // midpoints of a grid over the unit square, warped to evenly spread directions
+        let n = 512;
+        let integral = (0..n * n)
+            .map(|i| Vec2::new((i % n) as f32 + 0.5, (i / n) as f32 + 0.5) / n as f32)
+            .map(|u| mesh.pdf_value(Vec3::zeros(), uniform_sphere(u), 0.0))
+            .sum::<f32>() * 4.0 * PI / (n * n) as f32;
+
+        assert!((integral - 1.0).abs() < 0.02, "pdf integrates to {integral}");
+    }
+
+    #[test]
+    fn sampled_directions_reach_the_mesh() {
+        let mesh = tetrahedron();
+        let sampler = SobolSampler { seed: 2 };
+
+        for index in 0..256 {
+            let direction = mesh.random(Vec3::zeros(), 0.0, &mut SampleStream::new(&sampler, 0, 0, index));
+
+            assert!(mesh.pdf_value(Vec3::zeros(), direction, 0.0) > 0.0, "{direction} misses the mesh");
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::color::Color;
+use crate::integrator::{Integrator, PathSample, SceneContext, Termination};
+use crate::interval::Interval;
+use crate::pdf::Pdf;
+use crate::pdf::cosine::CosinePdf;
+use crate::ray::Ray;
+use crate::sampler::SampleStream;
+
+pub struct AmbientOcclusionIntegrator {
+    pub samples: u32,
+    // None uses a tenth of the scene's bounding box diagonal
+    pub distance: Option<f32>,
+}
+
+impl AmbientOcclusionIntegrator {
+    pub const DEFAULT_DISTANCE_FRACTION: f32 = 0.1;
+}
+
+impl Default for AmbientOcclusionIntegrator {
+    fn default() -> Self {
+        Self { samples: 1, distance: None }
+    }
+}
+
+impl Integrator for AmbientOcclusionIntegrator {
+    fn trace(&self, ray: Ray, scene: &SceneContext, sampler: &mut SampleStream) -> PathSample {
+        let Some(rec) = scene.hit(ray, sampler) else {
+            let radiance = Color::from_element(1.0);
+            return PathSample { radiance, direct: radiance, bounces: 0, termination: Termination::Escaped, first_hit: None };
+        };
+
+        let distance = self.distance.unwrap_or_else(|| {
+            let diagonal = scene.world.bounding_box().axes().map(Interval::size);
+            Color::from(diagonal).magnitude() * Self::DEFAULT_DISTANCE_FRACTION
+        });
+
+        let ray_t = Interval { min: SceneContext::MIN_RAY_DISTANCE, max: distance };
+
+        // cosine weighted directions cancel out the cosine term, leaving the unoccluded fraction
+        let pdf = CosinePdf::new(rec.normal);
+
+        let unoccluded = (0..self.samples)
+            .filter(|_| scene.world.hit(Ray { origin: rec.p, direction: pdf.generate(sampler), time: ray.time }, ray_t, sampler).is_none())
+            .count();
+
+        let radiance = Color::from_element(unoccluded as f32 / self.samples.max(1) as f32);
+
+        PathSample {
+            radiance,
+            direct: radiance,
+            bounces: 0,
+            termination: Termination::Truncated,
+            first_hit: Some(scene.first_hit(ray, &rec)),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::color::Color;
+use crate::integrator::{Integrator, PathSample, SceneContext, Termination};
+use crate::integrator::path::{sample_lights, weighted_emission};
+use crate::material::{ScatterKind, ScatterResult};
+use crate::ray::Ray;
+use crate::sampler::SampleStream;
+
+// only light arriving straight from an emitter or the background, specular surfaces are still followed
+pub struct DirectLightingIntegrator;
+
+impl Integrator for DirectLightingIntegrator {
+    fn trace(&self, mut ray: Ray, scene: &SceneContext, sampler: &mut SampleStream) -> PathSample {
+        let mut radiance = Color::zeros();
+        let mut throughput = Color::from_element(1.0);
+
+        // the radiance once the second hit's emission is added, everything found after that is indirect
+        let mut direct = None;
+        let mut first_hit = None;
+
+        let (bounces, termination) = 'path: {
+            for bounce in 0..scene.max_depth {
+                let Some(rec) = scene.hit(ray, sampler) else {
+                    radiance += throughput.component_mul(&scene.background_color);
+                    break 'path (bounce, Termination::Escaped);
+                };
+
+                if bounce == 0 {
+                    first_hit = Some(scene.first_hit(ray, &rec));
+                }
+
+                radiance += throughput.component_mul(&rec.material.emitted(rec.uv, rec.p));
+
+                if bounce == 1 {
+                    direct = Some(radiance);
+                }
+
+                let Some(ScatterResult { attenuation, kind }) = rec.material.scatter(ray, &rec, sampler) else {
+                    break 'path (bounce, Termination::Absorbed);
+                };
+
+                let material_pdf = match kind {
+                    ScatterKind::Specular(scattered) => {
+                        throughput.component_mul_assign(&attenuation);
+                        ray = scattered;
+                        continue;
+                    }
+                    ScatterKind::Diffuse(material_pdf) => material_pdf,
+                };
+
+                radiance += throughput.component_mul(&sample_lights(scene, ray, &rec, attenuation, material_pdf.as_ref(), sampler));
+
+                let scattered = Ray { origin: rec.p, direction: material_pdf.generate(sampler), time: ray.time };
+                let pdf_value = material_pdf.value(scattered.direction);
+
+                if pdf_value <= 0.0 {
+                    break 'path (bounce, Termination::Absorbed);
+                }
+
+                // the material sample only picks up whatever it sees directly, it never bounces again
+                let incoming_color = match scene.hit(scattered, sampler) {
+                    Some(light_rec) => weighted_emission(scene, scattered, &light_rec, Some(pdf_value)),
+                    None => scene.background_color,
+                };
+
+                let scattering_pdf = rec.material.scattering_pdf(ray, &rec, scattered);
+                let weight = attenuation * scattering_pdf / pdf_value;
+
+                radiance += throughput.component_mul(&weight.component_mul(&incoming_color));
+
+                break 'path (bounce + 1, Termination::Truncated);
+            }
+
+            (scene.max_depth, Termination::MaxDepth)
+        };
+
+        PathSample { radiance, direct: direct.unwrap_or(radiance), bounces, termination, first_hit }
+    }
+}
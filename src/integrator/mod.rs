@@ -0,0 +1,125 @@
+pub mod ambient_occlusion;
+pub mod direct;
+pub mod naive_path;
+pub mod normal;
+pub mod path;
+
+use glm::{Vec2, Vec3};
+use crate::color::Color;
+use crate::hittable::{HitRecord, Hittable};
+use crate::hittable::hittable_list::HittableList;
+use crate::integrator::ambient_occlusion::AmbientOcclusionIntegrator;
+use crate::integrator::direct::DirectLightingIntegrator;
+use crate::integrator::naive_path::NaivePathIntegrator;
+use crate::integrator::normal::NormalIntegrator;
+use crate::integrator::path::PathIntegrator;
+use crate::interval::Interval;
+use crate::material::MaterialIds;
+use crate::ray::Ray;
+use crate::sampler::SampleStream;
+
+pub trait Integrator: Send + Sync {
+    fn trace(&self, ray: Ray, scene: &SceneContext, sampler: &mut SampleStream) -> PathSample;
+
+    fn radiance(&self, ray: Ray, scene: &SceneContext, sampler: &mut SampleStream) -> Color {
+        self.trace(ray, scene, sampler).radiance
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct PathSample {
+    pub radiance: Color,
+    // the part of the radiance that reached the camera after at most one bounce, including emitters seen directly
+    pub direct: Color,
+    // scattering events along the path, not counting the camera ray
+    pub bounces: u16,
+    pub termination: Termination,
+    // None when the camera ray escaped
+    pub first_hit: Option<FirstHit>,
+}
+
+// what the camera ray hit, for the render's AOVs
+#[derive(Copy, Clone, Debug)]
+pub struct FirstHit {
+    // along the camera ray
+    pub depth: f32,
+    pub normal: Vec3,
+    pub albedo: Color,
+    pub uv: Vec2,
+    pub emission: Color,
+    pub object_id: u32,
+    pub material_id: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Termination {
+    // the ray left the scene and picked up the background
+    Escaped,
+    // the surface didn't scatter, or scattered in a direction it can't produce
+    Absorbed,
+    MaxDepth,
+    RussianRoulette,
+    // the integrator doesn't follow paths any further, e.g. direct lighting after the first diffuse bounce
+    Truncated,
+    // the camera had no ray to trace, outside of a fisheye's image circle
+    NoRay,
+}
+
+// everything an integrator gets to see of the scene being rendered
+pub struct SceneContext<'a> {
+    pub world: &'a dyn Hittable,
+    pub lights: &'a HittableList,
+    pub background_color: Color,
+    pub max_depth: u16,
+    pub min_depth: u16,
+    pub material_ids: MaterialIds,
+}
+
+impl SceneContext<'_> {
+    pub const MIN_RAY_DISTANCE: f32 = 0.001;
+    pub const MAX_SURVIVAL_PROBABILITY: f32 = 0.95;
+
+    pub fn hit(&self, ray: Ray, sampler: &mut SampleStream) -> Option<HitRecord> {
+        self.world.hit(ray, Interval { min: Self::MIN_RAY_DISTANCE, max: f32::INFINITY }, sampler)
+    }
+
+    pub fn first_hit(&self, ray: Ray, rec: &HitRecord) -> FirstHit {
+        FirstHit {
+            depth: rec.t * ray.direction.magnitude(),
+            normal: rec.normal,
+            albedo: rec.material.albedo(rec),
+            uv: rec.uv,
+            emission: rec.material.emitted(rec.uv, rec.p),
+            object_id: rec.object_id,
+            material_id: self.material_ids.get(&rec.material),
+        }
+    }
+
+    // russian roulette for a path about to continue after its `bounce`th bounce;
+    // None terminates it, otherwise survivors must be divided by the returned probability to stay unbiased
+    pub fn roulette(&self, bounce: u16, throughput: Color, sampler: &mut SampleStream) -> Option<f32> {
+        if bounce < self.min_depth {
+            return Some(1.0);
+        }
+
+        let survival_probability = throughput.max().min(Self::MAX_SURVIVAL_PROBABILITY);
+
+        (sampler.get_1d() < survival_probability).then_some(survival_probability)
+    }
+}
+
+pub type IntegratorBuilder = fn() -> Box<dyn Integrator>;
+
+pub const BUILTIN_INTEGRATORS: [(&str, IntegratorBuilder); 5] = [
+    ("path", || Box::new(PathIntegrator)),
+    ("naive", || Box::new(NaivePathIntegrator)),
+    ("direct", || Box::new(DirectLightingIntegrator)),
+    ("ao", || Box::new(AmbientOcclusionIntegrator::default())),
+    ("normals", || Box::new(NormalIntegrator)),
+];
+
+pub fn find(name: &str) -> Option<IntegratorBuilder> {
+    BUILTIN_INTEGRATORS.iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, builder)| *builder)
+}
@@ -0,0 +1,72 @@
+use crate::color::Color;
+use crate::integrator::{Integrator, PathSample, SceneContext, Termination};
+use crate::material::{ScatterKind, ScatterResult};
+use crate::ray::Ray;
+use crate::sampler::SampleStream;
+
+// path tracer that only follows material samples, so lights are only found by chance
+pub struct NaivePathIntegrator;
+
+impl Integrator for NaivePathIntegrator {
+    fn trace(&self, mut ray: Ray, scene: &SceneContext, sampler: &mut SampleStream) -> PathSample {
+        let mut radiance = Color::zeros();
+        // how much of the light arriving along the ray reaches the camera
+        let mut throughput = Color::from_element(1.0);
+
+        // the radiance once the second hit's emission is added, everything found after that is indirect
+        let mut direct = None;
+        let mut first_hit = None;
+
+        let (bounces, termination) = 'path: {
+            for bounce in 0..scene.max_depth {
+                let Some(rec) = scene.hit(ray, sampler) else {
+                    radiance += throughput.component_mul(&scene.background_color);
+                    break 'path (bounce, Termination::Escaped);
+                };
+
+                if bounce == 0 {
+                    first_hit = Some(scene.first_hit(ray, &rec));
+                }
+
+                radiance += throughput.component_mul(&rec.material.emitted(rec.uv, rec.p));
+
+                if bounce == 1 {
+                    direct = Some(radiance);
+                }
+
+                let Some(ScatterResult { attenuation, kind }) = rec.material.scatter(ray, &rec, sampler) else {
+                    break 'path (bounce, Termination::Absorbed);
+                };
+
+                let (scattered, weight) = match kind {
+                    ScatterKind::Specular(scattered) => (scattered, attenuation),
+                    ScatterKind::Diffuse(material_pdf) => {
+                        let scattered = Ray { origin: rec.p, direction: material_pdf.generate(sampler), time: ray.time };
+                        let pdf_value = material_pdf.value(scattered.direction);
+
+                        if pdf_value <= 0.0 {
+                            break 'path (bounce, Termination::Absorbed);
+                        }
+
+                        let scattering_pdf = rec.material.scattering_pdf(ray, &rec, scattered);
+
+                        (scattered, attenuation * scattering_pdf / pdf_value)
+                    }
+                };
+
+                throughput.component_mul_assign(&weight);
+
+                let Some(survival_probability) = scene.roulette(bounce, throughput, sampler) else {
+                    break 'path (bounce + 1, Termination::RussianRoulette);
+                };
+
+                throughput /= survival_probability;
+                ray = scattered;
+            }
+
+            (scene.max_depth, Termination::MaxDepth)
+        };
+
+        PathSample { radiance, direct: direct.unwrap_or(radiance), bounces, termination, first_hit }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::color::Color;
+use crate::integrator::{Integrator, PathSample, SceneContext, Termination};
+use crate::ray::Ray;
+use crate::sampler::SampleStream;
+
+// shows the shading normal of the first hit, mapped from [-1, 1] to [0, 1]
+pub struct NormalIntegrator;
+
+impl Integrator for NormalIntegrator {
+    fn trace(&self, ray: Ray, scene: &SceneContext, sampler: &mut SampleStream) -> PathSample {
+        match scene.hit(ray, sampler) {
+            Some(rec) => {
+                let radiance = 0.5 * (rec.normal + Color::from_element(1.0));
+
+                PathSample {
+                    radiance,
+                    direct: radiance,
+                    bounces: 0,
+                    termination: Termination::Truncated,
+                    first_hit: Some(scene.first_hit(ray, &rec)),
+                }
+            }
+            None => PathSample {
+                radiance: Color::zeros(),
+                direct: Color::zeros(),
+                bounces: 0,
+                termination: Termination::Escaped,
+                first_hit: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::color::Color;
+use crate::hittable::{HitRecord, Hittable};
+use crate::integrator::{Integrator, PathSample, SceneContext, Termination};
+use crate::material::{ScatterKind, ScatterResult};
+use crate::pdf::{Pdf, power_heuristic};
+use crate::pdf::hittable::HittablePdf;
+use crate::ray::Ray;
+use crate::sampler::SampleStream;
+
+// path tracer with next event estimation, light and material samples are combined with the power heuristic
+pub struct PathIntegrator;
+
+impl Integrator for PathIntegrator {
+    fn trace(&self, mut ray: Ray, scene: &SceneContext, sampler: &mut SampleStream) -> PathSample {
+        let mut radiance = Color::zeros();
+        // how much of the light arriving along the ray reaches the camera
+        let mut throughput = Color::from_element(1.0);
+        // the pdf the ray was sampled with, or None for camera rays and specular bounces
+        let mut bsdf_pdf = None;
+
+        // the radiance once the second hit's emission is added, everything found after that is indirect
+        let mut direct = None;
+        let mut first_hit = None;
+
+        let (bounces, termination) = 'path: {
+            for bounce in 0..scene.max_depth {
+                let Some(rec) = scene.hit(ray, sampler) else {
+                    radiance += throughput.component_mul(&scene.background_color);
+                    break 'path (bounce, Termination::Escaped);
+                };
+
+                if bounce == 0 {
+                    first_hit = Some(scene.first_hit(ray, &rec));
+                }
+
+                radiance += throughput.component_mul(&weighted_emission(scene, ray, &rec, bsdf_pdf));
+
+                if bounce == 1 {
+                    direct = Some(radiance);
+                }
+
+                let Some(ScatterResult { attenuation, kind }) = rec.material.scatter(ray, &rec, sampler) else {
+                    break 'path (bounce, Termination::Absorbed);
+                };
+
+                let (scattered, weight) = match kind {
+                    ScatterKind::Specular(scattered) => {
+                        bsdf_pdf = None;
+                        (scattered, attenuation)
+                    }
+                    ScatterKind::Diffuse(material_pdf) => {
+                        let direct_color = sample_lights(scene, ray, &rec, attenuation, material_pdf.as_ref(), sampler);
+                        radiance += throughput.component_mul(&direct_color);
+
+                        let scattered = Ray { origin: rec.p, direction: material_pdf.generate(sampler), time: ray.time };
+                        let pdf_value = material_pdf.value(scattered.direction);
+
+                        if pdf_value <= 0.0 {
+                            break 'path (bounce, Termination::Absorbed);
+                        }
+
+                        let scattering_pdf = rec.material.scattering_pdf(ray, &rec, scattered);
+
+                        bsdf_pdf = Some(pdf_value);
+                        (scattered, attenuation * scattering_pdf / pdf_value)
+                    }
+                };
+
+                throughput.component_mul_assign(&weight);
+
+                let Some(survival_probability) = scene.roulette(bounce, throughput, sampler) else {
+                    break 'path (bounce + 1, Termination::RussianRoulette);
+                };
+
+                throughput /= survival_probability;
+                ray = scattered;
+            }
+
+            (scene.max_depth, Termination::MaxDepth)
+        };
+
+        PathSample { radiance, direct: direct.unwrap_or(radiance), bounces, termination, first_hit }
+    }
+}
+
+// emission seen by a ray, weighted against the chance of next event estimation having found the same light
+pub(crate) fn weighted_emission(scene: &SceneContext, ray: Ray, rec: &HitRecord, bsdf_pdf: Option<f32>) -> Color {
+    let emission_color = rec.material.emitted(rec.uv, rec.p);
+
+    match bsdf_pdf {
+        Some(bsdf_pdf) if !scene.lights.objects.is_empty() && emission_color != Color::zeros() => {
+            let light_pdf = scene.lights.pdf_value(ray.origin, ray.direction, ray.time);
+            emission_color * power_heuristic(bsdf_pdf, light_pdf)
+        }
+        _ => emission_color,
+    }
+}
+
+pub(crate) fn sample_lights(scene: &SceneContext, ray: Ray, rec: &HitRecord, attenuation: Color, material_pdf: &dyn Pdf, sampler: &mut SampleStream) -> Color {
+    if scene.lights.objects.is_empty() {
+        return Color::zeros();
+    }
+
+    let light_pdf = HittablePdf { objects: scene.lights, origin: rec.p, time: ray.time };
+
+    let shadow_ray = Ray { origin: rec.p, direction: light_pdf.generate(sampler), time: ray.time };
+    let light_pdf_value = light_pdf.value(shadow_ray.direction);
+    let scattering_pdf = rec.material.scattering_pdf(ray, rec, shadow_ray);
+
+    if light_pdf_value <= 0.0 || scattering_pdf <= 0.0 {
+        return Color::zeros();
+    }
+
+    // whatever the shadow ray hits first is what's visible, so occluders contribute their own (usually zero) emission
+    let Some(light_rec) = scene.hit(shadow_ray, sampler) else {
+        return Color::zeros();
+    };
+
+    let emitted = light_rec.material.emitted(light_rec.uv, light_rec.p);
+    let weight = power_heuristic(light_pdf_value, material_pdf.value(shadow_ray.direction));
+
+    attenuation.component_mul(&emitted) * scattering_pdf * weight / light_pdf_value
+}
@@ -4,8 +4,14 @@ pub mod texture;
 pub mod aabb;
 pub mod camera;
 pub mod color;
+pub mod integrator;
 pub mod interval;
+pub mod obj;
+pub mod output;
+pub mod pdf;
 pub mod ray;
+pub mod sampler;
+pub mod scene;
 pub mod util;
 
 extern crate nalgebra_glm as glm;
@@ -15,11 +21,37 @@ extern crate nalgebra as na;
 pub mod prelude {
     pub use super::camera::{
         Camera,
+        aov::{Aov, AovSet},
         dimensions::CameraDimensions,
+        filter::{FilterKind, FilterMode, PixelFilter},
         focus_settings::FocusSettings,
-        render_quality::RenderQuality
+        model::{
+            CameraFrame, CameraModel, Viewport,
+            cubemap::CubemapCamera,
+            equirectangular::EquirectangularCamera,
+            fisheye::{FisheyeCamera, FisheyeMapping},
+            orthographic::OrthographicCamera,
+            perspective::PerspectiveCamera,
+            thin_lens::ThinLensCamera,
+        },
+        projection::Projection,
+        region::PixelRegion,
+        render_quality::{AdaptiveSampling, RenderQuality},
+        settings::CameraSettings,
+        shutter::Shutter,
+        tiles::{TileOrder, TileSettings},
+    };
+    pub use super::color::{Color, ColorSpace};
+    pub use super::integrator::{
+        Integrator,
+        PathSample,
+        Termination,
+        ambient_occlusion::AmbientOcclusionIntegrator,
+        direct::DirectLightingIntegrator,
+        naive_path::NaivePathIntegrator,
+        normal::NormalIntegrator,
+        path::PathIntegrator,
     };
-    pub use super::color::Color;
     pub use super::hittable::{
         bvh::BVHNode,
         constant_medium::ConstantMedium,
@@ -27,7 +59,9 @@ pub mod prelude {
         quad::Quad,
         rect_prism::RectangularPrism,
         sphere::Sphere,
-        transform::Transform,
+        transform::{Keyframe, Transform},
+        triangle::{DegenerateTriangle, Triangle},
+        triangle_mesh::{MeshFace, TriangleMesh, VertexBuffers},
     };
     pub use super::material::{
         Material,
@@ -43,6 +77,10 @@ pub mod prelude {
         perlin::{MarbleTexture, NoiseTexture},
         solid_color::SolidColor,
     };
+    pub use super::output::tone_map::{ToneMapOperator, ToneMapping};
+    pub use super::ray::Ray;
+    pub use super::sampler::SamplerKind;
+    pub use super::scene::Scene;
     pub use super::util::vec3_random as rand_vec;
     pub use glm::Vec3;
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 use glm::Vec3;
-use rand::Rng;
+use crate::color::Color;
 use crate::hittable::HitRecord;
-use crate::material::{Material, ScatterResult};
+use crate::material::{Material, ScatterKind, ScatterResult};
 use crate::ray::Ray;
+use crate::sampler::SampleStream;
 
 pub struct Dielectric { pub refraction_index: f32 }
 
@@ -15,14 +16,14 @@ impl Dielectric {
 }
 
 impl Material for Dielectric {
-    fn scatter(&self, ray: Ray, hit_record: &HitRecord) -> Option<ScatterResult> {
+    fn scatter(&self, ray: Ray, hit_record: &HitRecord, sampler: &mut SampleStream) -> Option<ScatterResult> {
         let unit_direction = ray.direction.normalize();
         let cos_theta = (-unit_direction).dot(&hit_record.normal).min(1.0);
         let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
 
         let ri = if hit_record.front_face { 1.0 / self.refraction_index } else { self.refraction_index };
 
-        let reflect = Dielectric::reflectance(cos_theta, ri) > rand::rng().random_range(0.0..1.0);
+        let reflect = Dielectric::reflectance(cos_theta, ri) > sampler.get_1d();
 
         let direction = if ri * sin_theta > 1.0 || reflect {
             glm::reflect_vec(&unit_direction, &hit_record.normal)
@@ -33,8 +34,13 @@ impl Material for Dielectric {
         Some(
             ScatterResult {
                 attenuation: Vec3::new(1.0, 1.0, 1.0),
-                scattered: Ray { origin: hit_record.p, direction },
+                kind: ScatterKind::Specular(Ray { origin: hit_record.p, direction, time: ray.time }),
             }
         )
     }
+
+    // clear glass lets everything through
+    fn albedo(&self, _hit_record: &HitRecord) -> Color {
+        Color::from_element(1.0)
+    }
 }
\ No newline at end of file
@@ -23,4 +23,8 @@ impl Material for DiffuseLight {
     fn emitted(&self, uv: Vec2, p: Vec3) -> Color {
         self.texture.value(uv, p)
     }
+
+    fn is_emissive(&self) -> bool {
+        true
+    }
 }
\ No newline at end of file
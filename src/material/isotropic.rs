@@ -1,11 +1,13 @@
+use std::f32::consts::PI;
 use std::sync::Arc;
 use crate::color::Color;
 use crate::hittable::HitRecord;
-use crate::material::{Material, ScatterResult};
+use crate::material::{Material, ScatterKind, ScatterResult};
+use crate::pdf::sphere::SpherePdf;
 use crate::ray::Ray;
 use crate::texture::solid_color::SolidColor;
 use crate::texture::Texture;
-use crate::util::vec3_random::random_vec_in_unit_sphere;
+use crate::sampler::SampleStream;
 
 pub struct Isotropic {
     pub texture: Arc<dyn Texture>,
@@ -18,14 +20,17 @@ impl Isotropic {
 }
 
 impl Material for Isotropic {
-    fn scatter(&self, _ray: Ray, hit_record: &HitRecord) -> Option<ScatterResult> {
-        let scattered = Ray {
-            origin: hit_record.p,
-            direction: random_vec_in_unit_sphere().normalize(),
-        };
-
+    fn scatter(&self, _ray: Ray, hit_record: &HitRecord, _sampler: &mut SampleStream) -> Option<ScatterResult> {
         let attenuation = self.texture.value(hit_record.uv, hit_record.p);
 
-        Some(ScatterResult { attenuation, scattered })
+        Some(ScatterResult { attenuation, kind: ScatterKind::Diffuse(Box::new(SpherePdf)) })
+    }
+
+    fn scattering_pdf(&self, _ray: Ray, _hit_record: &HitRecord, _scattered: Ray) -> f32 {
+        1.0 / (4.0 * PI)
     }
-}
\ No newline at end of file
+
+    fn albedo(&self, hit_record: &HitRecord) -> Color {
+        self.texture.value(hit_record.uv, hit_record.p)
+    }
+}
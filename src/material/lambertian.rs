@@ -1,17 +1,17 @@
+use std::f32::consts::PI;
 use std::sync::Arc;
 use crate::color::Color;
 use crate::hittable::HitRecord;
-use crate::material::{Material, ScatterResult};
+use crate::material::{Material, ScatterKind, ScatterResult};
+use crate::pdf::cosine::CosinePdf;
 use crate::ray::Ray;
 use crate::texture::solid_color::SolidColor;
 use crate::texture::Texture;
-use crate::util::vec3_random::random_vec_in_unit_sphere;
+use crate::sampler::SampleStream;
 
 pub struct Lambertian { pub texture: Arc<dyn Texture> }
 
 impl Lambertian {
-    pub const SCATTER_EPSILON: f32 = 1e-8;
-    
     pub fn from_albedo(albedo: Color) -> Self {
         Self {
             texture: Arc::new(SolidColor { albedo }),
@@ -20,18 +20,21 @@ impl Lambertian {
 }
 
 impl Material for Lambertian {
-    fn scatter(&self, _ray: Ray, hit_record: &HitRecord) -> Option<ScatterResult> {
-        let mut direction = hit_record.normal + random_vec_in_unit_sphere().normalize();
-
-        if direction.magnitude_squared() < Self::SCATTER_EPSILON {
-            direction = hit_record.normal;
-        }
-
+    fn scatter(&self, _ray: Ray, hit_record: &HitRecord, _sampler: &mut SampleStream) -> Option<ScatterResult> {
         Some(
             ScatterResult {
-                scattered: Ray { origin: hit_record.p, direction },
+                kind: ScatterKind::Diffuse(Box::new(CosinePdf::new(hit_record.normal))),
                 attenuation: self.texture.value(hit_record.uv, hit_record.p),
             }
         )
     }
-}
\ No newline at end of file
+
+    fn scattering_pdf(&self, _ray: Ray, hit_record: &HitRecord, scattered: Ray) -> f32 {
+        let cos_theta = hit_record.normal.dot(&scattered.direction.normalize());
+        (cos_theta / PI).max(0.0)
+    }
+
+    fn albedo(&self, hit_record: &HitRecord) -> Color {
+        self.texture.value(hit_record.uv, hit_record.p)
+    }
+}
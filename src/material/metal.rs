@@ -1,20 +1,25 @@
 use crate::color::Color;
 use crate::hittable::HitRecord;
-use crate::material::{Material, ScatterResult};
+use crate::material::{Material, ScatterKind, ScatterResult};
 use crate::ray::Ray;
-use crate::util::vec3_random::random_vec_in_unit_sphere;
+use crate::sampler::SampleStream;
+use crate::util::warp::uniform_sphere;
 
 pub struct Metal { pub albedo: Color, pub fuzz: f32 }
 
 impl Material for Metal {
-    fn scatter(&self, ray: Ray, hit_record: &HitRecord) -> Option<ScatterResult> {
+    fn scatter(&self, ray: Ray, hit_record: &HitRecord, sampler: &mut SampleStream) -> Option<ScatterResult> {
         let direction = glm::reflect_vec(&ray.direction, &hit_record.normal)
-            .normalize() + (self.fuzz * random_vec_in_unit_sphere().normalize());
+            .normalize() + (self.fuzz * uniform_sphere(sampler.get_2d()));
 
         (direction.dot(&hit_record.normal) > 0.0)
             .then_some(ScatterResult {
-                scattered: Ray { origin: hit_record.p, direction },
+                kind: ScatterKind::Specular(Ray { origin: hit_record.p, direction, time: ray.time }),
                 attenuation: self.albedo,
             })
     }
+
+    fn albedo(&self, _hit_record: &HitRecord) -> Color {
+        self.albedo
+    }
 }
\ No newline at end of file
@@ -4,26 +4,81 @@ pub mod dielectric;
 pub mod diffuse_light;
 pub mod isotropic;
 
+use std::collections::HashMap;
+use std::sync::Arc;
 use glm::{Vec2, Vec3};
 use crate::color::Color;
-use crate::hittable::HitRecord;
+use crate::hittable::{HitRecord, Hittable};
+use crate::pdf::Pdf;
 use crate::ray::Ray;
+use crate::sampler::SampleStream;
 
 pub struct ScatterResult {
     pub attenuation: Color,
-    pub scattered: Ray,
+    pub kind: ScatterKind,
+}
+
+pub enum ScatterKind {
+    Specular(Ray),
+    Diffuse(Box<dyn Pdf>),
 }
 
 // TODO: make enum with dynamic material
 
 pub trait Material: Send + Sync {
     #[expect(unused_variables, reason = "trait method")]
-    fn scatter(&self, ray: Ray, hit_record: &HitRecord) -> Option<ScatterResult> {
+    fn scatter(&self, ray: Ray, hit_record: &HitRecord, sampler: &mut SampleStream) -> Option<ScatterResult> {
         None
     }
 
+    #[expect(unused_variables, reason = "trait method")]
+    fn scattering_pdf(&self, ray: Ray, hit_record: &HitRecord, scattered: Ray) -> f32 {
+        0.0
+    }
+
     #[expect(unused_variables, reason = "trait method")]
     fn emitted(&self, uv: Vec2, p: Vec3) -> Color {
         Color::default()
     }
+
+    // whether the material emits light anywhere, so surfaces with it are worth sampling as lights
+    fn is_emissive(&self) -> bool {
+        false
+    }
+
+    // the surface's color, without any lighting, for the albedo AOV
+    #[expect(unused_variables, reason = "trait method")]
+    fn albedo(&self, hit_record: &HitRecord) -> Color {
+        Color::default()
+    }
+}
+
+// numbers a scene's materials from 1 in the order the scene visits them, so ids don't change between renders;
+// materials shared between objects get a single id
+#[derive(Default)]
+pub struct MaterialIds {
+    // keyed by the material's address
+    ids: HashMap<usize, u32>,
+}
+
+impl MaterialIds {
+    pub fn new(world: &dyn Hittable) -> Self {
+        let mut ids = HashMap::new();
+
+        world.visit_materials(&mut |material| {
+            let next_id = ids.len() as u32 + 1;
+            ids.entry(Self::key(material)).or_insert(next_id);
+        });
+
+        Self { ids }
+    }
+
+    // 0 for materials that weren't visited
+    pub fn get(&self, material: &Arc<dyn Material>) -> u32 {
+        self.ids.get(&Self::key(material)).copied().unwrap_or(0)
+    }
+
+    fn key(material: &Arc<dyn Material>) -> usize {
+        Arc::as_ptr(material) as *const () as usize
+    }
 }
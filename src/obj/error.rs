@@ -0,0 +1,54 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum ObjError {
+    Io { path: PathBuf, source: std::io::Error },
+    Texture { path: PathBuf, source: image::ImageError },
+    Malformed { path: PathBuf, line: usize, kind: MalformedLine },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MalformedLine {
+    MissingArguments { keyword: String, expected: usize, found: usize },
+    InvalidNumber(String),
+    InvalidIndex(String),
+    IndexOutOfRange { index: i64, len: usize },
+    UnknownMaterial(String),
+    NoActiveMaterial { keyword: String },
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjError::Io { path, source } => write!(f, "failed to read '{}': {source}", path.display()),
+            ObjError::Texture { path, source } => write!(f, "failed to load texture '{}': {source}", path.display()),
+            ObjError::Malformed { path, line, kind } => write!(f, "{}:{line}: {kind}", path.display()),
+        }
+    }
+}
+
+impl fmt::Display for MalformedLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MalformedLine::MissingArguments { keyword, expected, found } =>
+                write!(f, "'{keyword}' expects at least {expected} argument(s), found {found}"),
+            MalformedLine::InvalidNumber(token) => write!(f, "'{token}' is not a valid number"),
+            MalformedLine::InvalidIndex(token) => write!(f, "'{token}' is not a valid vertex reference"),
+            MalformedLine::IndexOutOfRange { index, len } =>
+                write!(f, "index {index} is out of range for {len} defined element(s)"),
+            MalformedLine::UnknownMaterial(name) => write!(f, "material '{name}' was never defined"),
+            MalformedLine::NoActiveMaterial { keyword } => write!(f, "'{keyword}' appears before any 'newmtl'"),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ObjError::Io { source, .. } => Some(source),
+            ObjError::Texture { source, .. } => Some(source),
+            ObjError::Malformed { .. } => None,
+        }
+    }
+}
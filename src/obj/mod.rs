@@ -0,0 +1,314 @@
+pub mod error;
+pub mod mtl;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use glm::{Vec2, Vec3};
+use crate::color::Color;
+use crate::hittable::hittable_list::HittableList;
+use crate::hittable::triangle_mesh::{MeshFace, TriangleMesh, VertexBuffers};
+use crate::material::Material;
+use crate::material::lambertian::Lambertian;
+use crate::obj::error::{MalformedLine, ObjError};
+
+pub fn load(path: impl AsRef<Path>) -> Result<HittableList, ObjError> {
+    load_with_default_material(path, Arc::new(Lambertian::from_albedo(Color::from_element(0.73))))
+}
+
+pub fn load_with_default_material(path: impl AsRef<Path>, default_material: Arc<dyn Material>) -> Result<HittableList, ObjError> {
+    let path = path.as_ref();
+    let source = read_file(path)?;
+    let directory = path.parent().unwrap_or(Path::new(""));
+
+    let mut buffers = VertexBuffers::default();
+    let mut materials: HashMap<String, Arc<dyn Material>> = HashMap::new();
+
+    // faces are grouped by material so every material becomes its own mesh
+    let mut groups: Vec<(Option<String>, Vec<MeshFace>)> = vec![(None, Vec::new())];
+    let mut current_group = 0;
+
+    for line in lines(path, &source) {
+        match line.keyword {
+            "v" => buffers.positions.push(Vec3::from(line.floats::<3>()?)),
+            "vn" => buffers.normals.push(Vec3::from(line.floats::<3>()?)),
+            "vt" => {
+                line.expect_args(1)?;
+
+                let u = line.parse_arg::<f32>(0)?;
+                let v = if line.args.len() > 1 { line.parse_arg::<f32>(1)? } else { 0.0 };
+
+                buffers.uvs.push(Vec2::new(u, v));
+            }
+            "f" => {
+                line.expect_args(3)?;
+
+                let refs = line.args.iter()
+                    .map(|arg| parse_vertex_ref(&line, arg, &buffers))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let faces = &mut groups[current_group].1;
+
+                // polygons are triangulated as a fan around the first vertex
+                for i in 1..refs.len() - 1 {
+                    let tri = [refs[0], refs[i], refs[i + 1]];
+
+                    faces.push(MeshFace {
+                        positions: tri.map(|r| r.position),
+                        normals: tri.iter().all(|r| r.normal.is_some())
+                            .then(|| tri.map(|r| r.normal.expect("checked above"))),
+                        uvs: tri.iter().all(|r| r.uv.is_some())
+                            .then(|| tri.map(|r| r.uv.expect("checked above"))),
+                    });
+                }
+            }
+            "mtllib" => {
+                line.expect_args(1)?;
+
+                for file in &line.args {
+                    materials.extend(mtl::load_mtl(directory.join(file))?);
+                }
+            }
+            "usemtl" => {
+                line.expect_args(1)?;
+
+                let name = line.args.join(" ");
+
+                if !materials.contains_key(&name) {
+                    return Err(line.error(MalformedLine::UnknownMaterial(name)));
+                }
+
+                current_group = match groups.iter().position(|(n, _)| n.as_ref() == Some(&name)) {
+                    Some(i) => i,
+                    None => {
+                        groups.push((Some(name), Vec::new()));
+                        groups.len() - 1
+                    }
+                };
+            }
+            _ => {}
+        }
+    }
+
+    let buffers = Arc::new(buffers);
+    // a file's groups are parts of one mesh, which gets a single object id
+    let mut list = HittableList::parts();
+
+    for (name, faces) in groups {
+        let material = match name {
+            Some(name) => materials[&name].clone(),
+            None => default_material.clone(),
+        };
+
+        // indices were checked while parsing, so only groups of nothing but degenerate faces are left out
+        if let Ok(mesh) = TriangleMesh::try_new(buffers.clone(), &faces, material) {
+            list.add(Arc::new(mesh));
+        }
+    }
+
+    Ok(list)
+}
+
+#[derive(Copy, Clone)]
+struct VertexRef {
+    position: usize,
+    uv: Option<usize>,
+    normal: Option<usize>,
+}
+
+fn parse_vertex_ref(line: &Line, arg: &str, buffers: &VertexBuffers) -> Result<VertexRef, ObjError> {
+    let mut parts = arg.split('/');
+
+    let mut next_index = |len: usize| -> Result<Option<usize>, ObjError> {
+        match parts.next() {
+            None | Some("") => Ok(None),
+            Some(part) => resolve_index(line, part, len).map(Some),
+        }
+    };
+
+    let position = next_index(buffers.positions.len())?
+        .ok_or_else(|| line.error(MalformedLine::InvalidIndex(arg.to_string())))?;
+    let uv = next_index(buffers.uvs.len())?;
+    let normal = next_index(buffers.normals.len())?;
+
+    Ok(VertexRef { position, uv, normal })
+}
+
+// obj indices start at 1, and negative indices count back from the most recent element
+fn resolve_index(line: &Line, token: &str, len: usize) -> Result<usize, ObjError> {
+    let index = token.parse::<i64>()
+        .map_err(|_| line.error(MalformedLine::InvalidIndex(token.to_string())))?;
+
+    let resolved = match index {
+        i if i > 0 => i - 1,
+        i if i < 0 => len as i64 + i,
+        _ => -1,
+    };
+
+    match usize::try_from(resolved) {
+        Ok(i) if i < len => Ok(i),
+        _ => Err(line.error(MalformedLine::IndexOutOfRange { index, len })),
+    }
+}
+
+pub(crate) struct Line<'a> {
+    path: &'a Path,
+    number: usize,
+    keyword: &'a str,
+    args: Vec<&'a str>,
+}
+
+impl Line<'_> {
+    fn error(&self, kind: MalformedLine) -> ObjError {
+        ObjError::Malformed { path: self.path.to_path_buf(), line: self.number, kind }
+    }
+
+    fn expect_args(&self, expected: usize) -> Result<(), ObjError> {
+        if self.args.len() < expected {
+            Err(self.error(MalformedLine::MissingArguments {
+                keyword: self.keyword.to_string(),
+                expected,
+                found: self.args.len(),
+            }))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn parse_arg<T: FromStr>(&self, i: usize) -> Result<T, ObjError> {
+        self.args[i].parse()
+            .map_err(|_| self.error(MalformedLine::InvalidNumber(self.args[i].to_string())))
+    }
+
+    fn floats<const N: usize>(&self) -> Result<[f32; N], ObjError> {
+        self.expect_args(N)?;
+
+        let mut values = [0.0; N];
+
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = self.parse_arg(i)?;
+        }
+
+        Ok(values)
+    }
+}
+
+pub(crate) fn lines<'a>(path: &'a Path, source: &'a str) -> impl Iterator<Item = Line<'a>> {
+    source.lines()
+        .enumerate()
+        .filter_map(move |(i, line)| {
+            let content = line.split('#').next().unwrap_or_default();
+            let mut tokens = content.split_whitespace();
+
+            tokens.next().map(|keyword| Line {
+                path,
+                number: i + 1,
+                keyword,
+                args: tokens.collect(),
+            })
+        })
+}
+
+pub(crate) fn read_file(path: &Path) -> Result<String, ObjError> {
+    std::fs::read_to_string(path)
+        .map_err(|source| ObjError::Io { path: PathBuf::from(path), source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::Hittable;
+
+    // written next to the other test files so concurrent tests don't share one
+    fn load_source(name: &str, source: &str) -> Result<HittableList, ObjError> {
+        let path = std::env::temp_dir().join(format!("raytracer-{}-{name}.obj", std::process::id()));
+        std::fs::write(&path, source).expect("temporary file should be writable");
+
+        let result = load(&path);
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    fn malformed(result: Result<HittableList, ObjError>) -> (usize, MalformedLine) {
+        match result {
+            Err(ObjError::Malformed { line, kind, .. }) => (line, kind),
+            Err(err) => panic!("expected a malformed line, got {err}"),
+            Ok(_) => panic!("expected the file to fail to load"),
+        }
+    }
+
+    fn resolve(token: &str, len: usize) -> Result<usize, MalformedLine> {
+        let line = Line { path: Path::new("test.obj"), number: 1, keyword: "f", args: Vec::new() };
+
+        resolve_index(&line, token, len).map_err(|err| match err {
+            ObjError::Malformed { kind, .. } => kind,
+            err => panic!("unexpected error {err}"),
+        })
+    }
+
+    #[test]
+    fn resolves_positive_and_negative_indices() {
+        assert_eq!(resolve("1", 3), Ok(0));
+        assert_eq!(resolve("3", 3), Ok(2));
+        assert_eq!(resolve("-1", 3), Ok(2));
+        assert_eq!(resolve("-3", 3), Ok(0));
+    }
+
+    #[test]
+    fn rejects_out_of_range_indices() {
+        assert_eq!(resolve("0", 3), Err(MalformedLine::IndexOutOfRange { index: 0, len: 3 }));
+        assert_eq!(resolve("4", 3), Err(MalformedLine::IndexOutOfRange { index: 4, len: 3 }));
+        assert_eq!(resolve("-4", 3), Err(MalformedLine::IndexOutOfRange { index: -4, len: 3 }));
+        assert_eq!(resolve("1", 0), Err(MalformedLine::IndexOutOfRange { index: 1, len: 0 }));
+        assert_eq!(resolve("one", 3), Err(MalformedLine::InvalidIndex("one".into())));
+    }
+
+    #[test]
+    fn negative_indices_count_back_from_the_latest_vertex() {
+        // the second quad only uses the last four vertices
+        let source = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\nv 0 0 5\nv 2 0 5\nv 2 2 5\nv 0 2 5\nf -4 -3 -2 -1\n";
+        let list = load_source("negative", source).expect("file should load");
+
+        let aabb = list.bounding_box();
+        assert_eq!((aabb.x.min, aabb.x.max, aabb.y.max), (0.0, 2.0, 2.0));
+        assert!(aabb.z.min < 0.001 && aabb.z.max > 4.999);
+    }
+
+    #[test]
+    fn reads_vertex_references_with_and_without_attributes() {
+        let source = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvt 0 0\nvt 1 0\nvt 0 1\nvn 0 0 1\nf 1/1/1 2/2/1 3/3/1\nf 1//1 2//1 3//1\nf 1/1 2/2 3/3\n";
+
+        assert_eq!(load_source("attributes", source).expect("file should load").objects.len(), 1);
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_index() {
+        let (line, kind) = malformed(load_source("out-of-range", "v 0 0 0\nv 1 0 0\nf 1 2 5\n"));
+        assert_eq!(line, 3);
+        assert_eq!(kind, MalformedLine::IndexOutOfRange { index: 5, len: 2 });
+
+        // normals are counted separately from positions
+        let (line, kind) = malformed(load_source("normal-out-of-range", "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\n\nf 1//1 2//1 3//2\n"));
+        assert_eq!(line, 6);
+        assert_eq!(kind, MalformedLine::IndexOutOfRange { index: 2, len: 1 });
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert_eq!(malformed(load_source("short-vertex", "v 0 0\n")).1, MalformedLine::MissingArguments { keyword: "v".into(), expected: 3, found: 2 });
+        assert_eq!(malformed(load_source("bad-number", "v 0 zero 0\n")).1, MalformedLine::InvalidNumber("zero".into()));
+        assert_eq!(malformed(load_source("short-face", "v 0 0 0\nv 1 0 0\nf 1 2\n")).1, MalformedLine::MissingArguments { keyword: "f".into(), expected: 3, found: 2 });
+        assert_eq!(malformed(load_source("no-position", "v 0 0 0\nf /1 /1 /1\n")).1, MalformedLine::InvalidIndex("/1".into()));
+        assert_eq!(malformed(load_source("unknown-material", "usemtl chrome\n")).1, MalformedLine::UnknownMaterial("chrome".into()));
+    }
+
+    #[test]
+    fn skips_degenerate_faces() {
+        let source = "v 0 0 0\nv 1 0 0\nv 2 0 0\nv 0 1 0\nf 1 2 3\nf 1 2 4\n";
+        let list = load_source("degenerate", source).expect("file should load");
+
+        let aabb = list.bounding_box();
+        assert_eq!((aabb.x.max, aabb.y.max), (1.0, 1.0));
+    }
+}
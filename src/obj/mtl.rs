@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::color::{Color, ColorSpace};
+use crate::material::Material;
+use crate::material::dielectric::Dielectric;
+use crate::material::diffuse_light::DiffuseLight;
+use crate::material::lambertian::Lambertian;
+use crate::material::metal::Metal;
+use crate::obj::{Line, lines, read_file};
+use crate::obj::error::{MalformedLine, ObjError};
+use crate::texture::image::ImageTexture;
+
+#[derive(Clone, Debug)]
+pub struct MtlDescription {
+    pub name: String,
+    pub diffuse: Color,
+    pub specular: Color,
+    pub emission: Color,
+    pub shininess: f32,
+    pub refraction_index: Option<f32>,
+    pub dissolve: f32,
+    pub diffuse_map: Option<PathBuf>,
+}
+
+impl MtlDescription {
+    pub const DEFAULT_REFRACTION_INDEX: f32 = 1.5;
+
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            diffuse: Color::from_element(0.8),
+            specular: Color::zeros(),
+            emission: Color::zeros(),
+            shininess: 0.0,
+            refraction_index: None,
+            dissolve: 1.0,
+            diffuse_map: None,
+        }
+    }
+
+    pub fn to_material(&self, textures: &mut HashMap<PathBuf, Arc<ImageTexture>>) -> Result<Arc<dyn Material>, ObjError> {
+        if self.emission.max() > 0.0 {
+            return Ok(Arc::new(DiffuseLight::from_emission_color(self.emission)));
+        }
+
+        if self.dissolve < 1.0 {
+            return Ok(Arc::new(Dielectric {
+                refraction_index: self.refraction_index.unwrap_or(Self::DEFAULT_REFRACTION_INDEX),
+            }));
+        }
+
+        if self.diffuse_map.is_none() && self.specular.max() > self.diffuse.max() {
+            // phong exponent to roughness, which is close enough to how fuzz behaves
+            let fuzz = (2.0 / (self.shininess.max(0.0) + 2.0)).sqrt();
+
+            return Ok(Arc::new(Metal { albedo: self.specular, fuzz }));
+        }
+
+        match &self.diffuse_map {
+            Some(path) => {
+                let texture = match textures.get(path) {
+                    Some(texture) => texture.clone(),
+                    None => {
+                        // diffuse maps hold colors, so they're stored as sRGB
+                        let texture = ImageTexture::open(path, ColorSpace::Srgb)
+                            .map_err(|source| ObjError::Texture { path: path.clone(), source })?;
+
+                        let texture = Arc::new(texture);
+                        textures.insert(path.clone(), texture.clone());
+                        texture
+                    }
+                };
+
+                Ok(Arc::new(Lambertian { texture }))
+            }
+            None => Ok(Arc::new(Lambertian::from_albedo(self.diffuse))),
+        }
+    }
+}
+
+pub fn parse_mtl(path: impl AsRef<Path>) -> Result<Vec<MtlDescription>, ObjError> {
+    let path = path.as_ref();
+    let source = read_file(path)?;
+    let directory = path.parent().unwrap_or(Path::new(""));
+
+    let mut descriptions: Vec<MtlDescription> = Vec::new();
+
+    for line in lines(path, &source) {
+        if line.keyword == "newmtl" {
+            line.expect_args(1)?;
+            descriptions.push(MtlDescription::new(line.args.join(" ")));
+            continue;
+        }
+
+        let Some(current) = descriptions.last_mut() else {
+            return Err(line.error(MalformedLine::NoActiveMaterial { keyword: line.keyword.to_string() }));
+        };
+
+        match line.keyword {
+            "Kd" => current.diffuse = parse_color(&line)?,
+            "Ks" => current.specular = parse_color(&line)?,
+            "Ke" => current.emission = parse_color(&line)?,
+            "Ns" => current.shininess = line.floats::<1>()?[0],
+            "Ni" => current.refraction_index = Some(line.floats::<1>()?[0]),
+            "d" => current.dissolve = line.floats::<1>()?[0],
+            "Tr" => current.dissolve = 1.0 - line.floats::<1>()?[0],
+            "map_Kd" => {
+                line.expect_args(1)?;
+                // texture options come before the file name, so the file name is always last
+                let file = line.args.last().expect("checked above");
+                current.diffuse_map = Some(directory.join(file));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(descriptions)
+}
+
+pub fn load_mtl(path: impl AsRef<Path>) -> Result<HashMap<String, Arc<dyn Material>>, ObjError> {
+    let mut textures = HashMap::new();
+
+    parse_mtl(path)?
+        .into_iter()
+        .map(|desc| Ok((desc.name.clone(), desc.to_material(&mut textures)?)))
+        .collect()
+}
+
+fn parse_color(line: &Line) -> Result<Color, ObjError> {
+    // a single component is shorthand for a grey color
+    match line.args.len() {
+        1 => Ok(Color::from_element(line.floats::<1>()?[0])),
+        _ => Ok(Color::from(line.floats::<3>()?)),
+    }
+}
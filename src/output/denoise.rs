@@ -0,0 +1,260 @@
+use image::{Rgb, Rgb32FImage};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use rayon::slice::ParallelSliceMut;
+use crate::camera::aov::Aov;
+use crate::color::Color;
+
+// the first hit's surface properties for every pixel, as gathered by the render's AOVs
+#[derive(Copy, Clone)]
+pub struct DenoiseFeatures<'a> {
+    pub albedo: &'a Rgb32FImage,
+    pub normal: &'a Rgb32FImage,
+    pub depth: &'a Rgb32FImage,
+    pub emission: &'a Rgb32FImage,
+}
+
+impl<'a> DenoiseFeatures<'a> {
+    pub const AOVS: [Aov; 4] = [Aov::Albedo, Aov::Normal, Aov::Depth, Aov::Emission];
+
+    // None unless all of the features are there
+    pub fn from_aovs(aovs: &'a [(Aov, Rgb32FImage)]) -> Option<Self> {
+        let find = |wanted: Aov| aovs.iter().find(|(aov, _)| *aov == wanted).map(|(_, image)| image);
+
+        Some(Self {
+            albedo: find(Aov::Albedo)?,
+            normal: find(Aov::Normal)?,
+            depth: find(Aov::Depth)?,
+            emission: find(Aov::Emission)?,
+        })
+    }
+}
+
+// edge avoiding à-trous wavelet filter (Dammertz et al., "Edge-Avoiding À-Trous Wavelet Transform for fast Global
+// Illumination Filtering"): a 5x5 B-spline kernel is applied with growing gaps between its taps, and every tap is
+// weighted by how similar its color, normal and depth are to the pixel's. only the light reflected by the first hit is
+// filtered, with its albedo divided out, so textures and the edges of lights stay sharp
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Denoiser {
+    // each one doubles the filter's reach, 5 covers about 60 pixels
+    pub iterations: u32,
+    // of the lighting, which is compared as c / (1 + c) so bright and dark areas are treated alike; halves every iteration
+    pub color_sigma: f32,
+    // exponent of the normals' dot product
+    pub normal_power: f32,
+    // in units of how much the depth changes between the pixel and its neighbors, so tilted surfaces are still smoothed
+    pub depth_sigma: f32,
+}
+
+impl Default for Denoiser {
+    fn default() -> Self {
+        Self { iterations: 5, color_sigma: 0.2, normal_power: 64.0, depth_sigma: 0.25 }
+    }
+}
+
+impl Denoiser {
+    const KERNEL: [f32; 5] = [1.0 / 16.0, 1.0 / 4.0, 3.0 / 8.0, 1.0 / 4.0, 1.0 / 16.0];
+    // albedo is raised to at least this before it's divided out, pixels that don't reach it in any channel (misses,
+    // lights and black surfaces) keep their color as is
+    const MIN_ALBEDO: f32 = 0.001;
+    // depth differences allowed on top of the gradient, relative to the depth, for surfaces facing the camera
+    const DEPTH_TOLERANCE: f32 = 0.001;
+
+    pub fn denoise(&self, image: &Rgb32FImage, features: DenoiseFeatures) -> Rgb32FImage {
+        let (width, height) = image.dimensions();
+
+        assert!(
+            [features.albedo, features.normal, features.depth, features.emission].iter().all(|feature| feature.dimensions() == (width, height)),
+            "Feature buffers must have the image's dimensions"
+        );
+
+        let pixel = |image: &Rgb32FImage, i: usize| Color::from(image.get_pixel(i as u32 % width, i as u32 / width).0);
+
+        let count = (width * height) as usize;
+
+        let albedo: Vec<Color> = (0..count)
+            .map(|i| {
+                let albedo = pixel(features.albedo, i);
+
+                if albedo.max() < Self::MIN_ALBEDO {
+                    Color::from_element(1.0)
+                } else {
+                    albedo.map(|v| v.max(Self::MIN_ALBEDO))
+                }
+            })
+            .collect();
+
+        let normals: Vec<Color> = (0..count).map(|i| pixel(features.normal, i)).collect();
+        let depths: Vec<f32> = (0..count).map(|i| pixel(features.depth, i).x).collect();
+        let gradients = depth_gradients(&depths, width as usize, height as usize);
+
+        let emission: Vec<Color> = (0..count).map(|i| pixel(features.emission, i)).collect();
+
+        let mut lighting: Vec<Color> = (0..count)
+            .map(|i| (pixel(image, i) - emission[i]).component_div(&albedo[i]))
+            .collect();
+
+        for iteration in 0..self.iterations {
+            let step = 1 << iteration;
+            let color_sigma = self.color_sigma * 0.5f32.powi(iteration as i32);
+
+            let guide = self.guide(&lighting, &normals, &depths, &gradients, width as usize, height as usize);
+            let mut filtered = vec![Color::zeros(); count];
+
+            filtered.par_chunks_mut(width as usize).enumerate().for_each(|(y, row)| {
+                for (x, out) in row.iter_mut().enumerate() {
+                    let p = y * width as usize + x;
+
+                    let mut sum = Color::zeros();
+                    let mut weight_sum = 0.0;
+
+                    for (ky, &hy) in Self::KERNEL.iter().enumerate() {
+                        let qy = y as i64 + (ky as i64 - 2) * step;
+
+                        if qy < 0 || qy >= height as i64 {
+                            continue;
+                        }
+
+                        for (kx, &hx) in Self::KERNEL.iter().enumerate() {
+                            let qx = x as i64 + (kx as i64 - 2) * step;
+
+                            if qx < 0 || qx >= width as i64 {
+                                continue;
+                            }
+
+                            let q = qy as usize * width as usize + qx as usize;
+
+                            // a single bad sample would otherwise spread over everything within the kernel's reach
+                            if !is_finite(lighting[q]) {
+                                continue;
+                            }
+
+                            let distance = step as f32 * ((kx as f32 - 2.0).powi(2) + (ky as f32 - 2.0).powi(2)).sqrt();
+                            let depth_scale = self.depth_sigma * gradients[p] * distance + Self::DEPTH_TOLERANCE * depths[p];
+
+                            let weight = hx * hy
+                                * self.color_weight(guide[p], guide[q], color_sigma)
+                                * self.geometry_weight(normals[p], normals[q], depths[p], depths[q], depth_scale);
+
+                            sum += weight * lighting[q];
+                            weight_sum += weight;
+                        }
+                    }
+
+                    // the pixel itself always has a weight, unless its color isn't finite
+                    *out = if weight_sum > 0.0 { sum / weight_sum } else { lighting[p] };
+                }
+            });
+
+            lighting = filtered;
+        }
+
+        Rgb32FImage::from_fn(width, height, |x, y| {
+            let i = (y * width + x) as usize;
+            Rgb((lighting[i].component_mul(&albedo[i]) + emission[i]).into())
+        })
+    }
+
+    // the lighting blurred over 3x3 pixels of the same surface, which colors are compared on instead of the noisy pixels,
+    // so a bright outlier isn't rejected by all of its neighbors and its energy kept out of the image
+    fn guide(&self, lighting: &[Color], normals: &[Color], depths: &[f32], gradients: &[f32], width: usize, height: usize) -> Vec<Color> {
+        (0..lighting.len())
+            .into_par_iter()
+            .map(|p| {
+                let (x, y) = (p % width, p / width);
+
+                let mut sum = Color::zeros();
+                let mut weight_sum = 0.0;
+
+                for qy in y.saturating_sub(1)..(y + 2).min(height) {
+                    for qx in x.saturating_sub(1)..(x + 2).min(width) {
+                        let q = qy * width + qx;
+                        let distance = ((qx as f32 - x as f32).powi(2) + (qy as f32 - y as f32).powi(2)).sqrt();
+                        let depth_scale = self.depth_sigma * gradients[p] * distance + Self::DEPTH_TOLERANCE * depths[p];
+                        let weight = self.geometry_weight(normals[p], normals[q], depths[p], depths[q], depth_scale);
+
+                        if is_finite(lighting[q]) {
+                            sum += weight * lighting[q];
+                            weight_sum += weight;
+                        }
+                    }
+                }
+
+                if weight_sum > 0.0 { sum / weight_sum } else { lighting[p] }
+            })
+            .collect()
+    }
+
+    fn color_weight(&self, p: Color, q: Color, sigma: f32) -> f32 {
+        let compress = |c: Color| c.map(|v| v.max(0.0) / (1.0 + v.max(0.0)));
+
+        (-(compress(p) - compress(q)).magnitude_squared() / (sigma * sigma)).exp()
+    }
+
+    // pixels where the camera ray escaped have no normal, and are only mixed with each other
+    fn geometry_weight(&self, normal_p: Color, normal_q: Color, depth_p: f32, depth_q: f32, depth_scale: f32) -> f32 {
+        match (depth_p.is_finite(), depth_q.is_finite()) {
+            (false, false) => 1.0,
+            (true, true) => {
+                let normal_weight = normal_p.dot(&normal_q).max(0.0).powf(self.normal_power);
+                let depth_weight = (-(depth_p - depth_q).abs() / depth_scale.max(f32::EPSILON)).exp();
+
+                normal_weight * depth_weight
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+// largest change in depth to a horizontal or vertical neighbor, per pixel, ignoring escaped rays
+fn depth_gradients(depths: &[f32], width: usize, height: usize) -> Vec<f32> {
+    (0..depths.len())
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+
+            let neighbors = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < width).then(|| i + 1),
+                (y > 0).then(|| i - width),
+                (y + 1 < height).then(|| i + width),
+            ];
+
+            neighbors.into_iter()
+                .flatten()
+                .map(|j| (depths[j] - depths[i]).abs())
+                .filter(|difference| difference.is_finite())
+                .fold(0.0, f32::max)
+        })
+        .collect()
+}
+
+fn is_finite(color: Color) -> bool {
+    color.iter().all(|v| v.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_finite_pixels_dont_spread() {
+        let (width, height) = (32, 32);
+        let uniform = |value: [f32; 3]| Rgb32FImage::from_pixel(width, height, Rgb(value));
+
+        let mut image = uniform([0.25, 0.5, 0.75]);
+        image.put_pixel(16, 16, Rgb([f32::NAN, 0.5, 0.75]));
+        image.put_pixel(4, 4, Rgb([f32::INFINITY; 3]));
+
+        let (albedo, normal, depth, emission) = (uniform([0.5; 3]), uniform([0.0, 0.0, 1.0]), uniform([1.0; 3]), uniform([0.0; 3]));
+        let features = DenoiseFeatures { albedo: &albedo, normal: &normal, depth: &depth, emission: &emission };
+
+        let denoised = Denoiser::default().denoise(&image, features);
+
+        for (x, y, pixel) in denoised.enumerate_pixels() {
+            assert!(pixel.0.iter().all(|v| v.is_finite()), "pixel ({x}, {y}) isn't finite: {:?}", pixel.0);
+
+            for (value, expected) in pixel.0.iter().zip([0.25, 0.5, 0.75]) {
+                assert!((value - expected).abs() < 1e-4, "pixel ({x}, {y}) is {:?}", pixel.0);
+            }
+        }
+    }
+}
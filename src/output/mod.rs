@@ -0,0 +1,164 @@
+pub mod denoise;
+pub mod pfm;
+pub mod tone_map;
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use exr::prelude::{AnyChannel, AnyChannels, Encoding, FlatSamples, Image, Layer, LayerAttributes, SmallVec, WritableImage};
+use image::{GrayImage, ImageError, ImageFormat, ImageResult, Luma, Rgb, Rgb32FImage, RgbImage};
+use image::codecs::hdr::HdrEncoder;
+use image::error::{EncodingError, ImageFormatHint};
+use crate::camera::aov::Aov;
+use crate::camera::progressive::SampleCountMap;
+use crate::color::{Color, transform_color_to_pixel};
+use crate::output::tone_map::ToneMapping;
+use crate::util::hash::hash;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    OpenExr,
+    Hdr,
+    Pfm,
+    // any 8-bit format the image crate can write, converted from the float buffer on save
+    Ldr(ImageFormat),
+}
+
+impl OutputFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "exr" => Some(Self::OpenExr),
+            "hdr" => Some(Self::Hdr),
+            "pfm" => Some(Self::Pfm),
+            _ => ImageFormat::from_extension(extension)
+                .filter(|format| format.writing_enabled())
+                .map(Self::Ldr),
+        }
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        path.as_ref().extension()
+            .and_then(|extension| Self::from_extension(&extension.to_string_lossy()))
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::OpenExr => "exr",
+            Self::Hdr => "hdr",
+            Self::Pfm => "pfm",
+            Self::Ldr(format) => format.extensions_str().first().copied().unwrap_or("png"),
+        }
+    }
+}
+
+// tone mapping only applies to 8-bit formats, float formats keep the raw radiance
+pub fn save(image: &Rgb32FImage, path: impl AsRef<Path>, format: OutputFormat, tone_mapping: &ToneMapping) -> ImageResult<()> {
+    let path = path.as_ref();
+
+    match format {
+        OutputFormat::OpenExr => image.save_with_format(path, ImageFormat::OpenExr),
+        OutputFormat::Hdr => {
+            let writer = BufWriter::new(File::create(path)?);
+            let pixels = image.pixels().copied().collect::<Vec<_>>();
+
+            HdrEncoder::new(writer).encode(&pixels, image.width() as usize, image.height() as usize)
+        }
+        OutputFormat::Pfm => Ok(pfm::write_pfm(BufWriter::new(File::create(path)?), image)?),
+        OutputFormat::Ldr(format) => to_ldr(image, tone_mapping).save_with_format(path, format),
+    }
+}
+
+pub fn to_ldr(image: &Rgb32FImage, tone_mapping: &ToneMapping) -> RgbImage {
+    RgbImage::from_fn(image.width(), image.height(), |x, y| {
+        transform_color_to_pixel(tone_mapping.apply(Color::from(image.get_pixel(x, y).0)))
+    })
+}
+
+// brighter pixels took more samples, scaled so the most sampled pixel is white
+pub fn sample_count_image(counts: &SampleCountMap) -> GrayImage {
+    let max = counts.pixels().map(|count| count.0[0]).max().unwrap_or(0).max(1);
+
+    GrayImage::from_fn(counts.width(), counts.height(), |x, y| {
+        Luma([(counts.get_pixel(x, y).0[0] as u64 * 255 / max as u64) as u8])
+    })
+}
+
+// float formats keep the AOV's raw values, 8-bit formats get a viewable version of it
+pub fn save_aov(image: &Rgb32FImage, aov: Aov, path: impl AsRef<Path>, format: OutputFormat, tone_mapping: &ToneMapping) -> ImageResult<()> {
+    match format {
+        OutputFormat::Ldr(format) => aov_to_ldr(image, aov, tone_mapping).save_with_format(path, format),
+        _ => save(image, path, format, tone_mapping),
+    }
+}
+
+// light is tone mapped like the image and albedo is sRGB encoded; data is scaled into [0, 1] without any encoding:
+// depth over the farthest hit, with escaped rays white, normals from [-1, 1], and sample counts over the largest.
+// ids get arbitrary but distinct colors
+pub fn aov_to_ldr(image: &Rgb32FImage, aov: Aov, tone_mapping: &ToneMapping) -> RgbImage {
+    let max_value = || image.pixels()
+        .map(|pixel| pixel.0[0])
+        .filter(|value| value.is_finite())
+        .fold(0.0, f32::max)
+        .max(f32::EPSILON);
+
+    let data = |color: Color| Rgb(color.map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8).into());
+
+    match aov {
+        Aov::Emission | Aov::Direct | Aov::Indirect => to_ldr(image, tone_mapping),
+        Aov::Albedo => RgbImage::from_fn(image.width(), image.height(), |x, y| {
+            transform_color_to_pixel(Color::from(image.get_pixel(x, y).0))
+        }),
+        Aov::Uv => RgbImage::from_fn(image.width(), image.height(), |x, y| data(Color::from(image.get_pixel(x, y).0))),
+        Aov::Normal => RgbImage::from_fn(image.width(), image.height(), |x, y| {
+            let normal = Color::from(image.get_pixel(x, y).0);
+
+            if normal == Color::zeros() { Rgb([0; 3]) } else { data(0.5 * (normal + Color::from_element(1.0))) }
+        }),
+        Aov::Depth | Aov::SampleCount => {
+            let max = max_value();
+
+            RgbImage::from_fn(image.width(), image.height(), |x, y| {
+                data(Color::from_element((image.get_pixel(x, y).0[0] / max).min(1.0)))
+            })
+        }
+        Aov::ObjectId | Aov::MaterialId => RgbImage::from_fn(image.width(), image.height(), |x, y| {
+            match image.get_pixel(x, y).0[0] as u64 {
+                0 => Rgb([0; 3]),
+                id => {
+                    let [r, g, b, ..] = hash([id]).to_le_bytes();
+                    Rgb([r, g, b])
+                }
+            }
+        }),
+    }
+}
+
+// one OpenEXR file holding the image as its R, G & B channels, and each AOV as channels prefixed by its name
+pub fn save_exr_layers(image: &Rgb32FImage, aovs: &[(Aov, Rgb32FImage)], path: impl AsRef<Path>) -> ImageResult<()> {
+    let channel = |name: String, image: &Rgb32FImage, component: usize| {
+        AnyChannel::new(name.as_str(), FlatSamples::F32(image.pixels().map(|pixel| pixel.0[component]).collect()))
+    };
+
+    let mut channels: Vec<_> = ["R", "G", "B"].into_iter()
+        .enumerate()
+        .map(|(component, name)| channel(name.to_string(), image, component))
+        .collect();
+
+    for (aov, image) in aovs {
+        for (component, name) in aov.channels().iter().enumerate() {
+            channels.push(channel(format!("{}.{name}", aov.name()), image, component));
+        }
+    }
+
+    let layer = Layer::new(
+        (image.width() as usize, image.height() as usize),
+        LayerAttributes::default(),
+        Encoding::FAST_LOSSLESS,
+        AnyChannels::sort(SmallVec::from_vec(channels)),
+    );
+
+    Image::from_layer(layer).write().to_file(path).map_err(|err| match err {
+        exr::error::Error::Io(err) => ImageError::IoError(err),
+        err => ImageError::Encoding(EncodingError::new(ImageFormatHint::Exact(ImageFormat::OpenExr), err)),
+    })
+}
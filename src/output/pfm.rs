@@ -0,0 +1,18 @@
+use std::io::{self, Write};
+use image::Rgb32FImage;
+
+// portable float map: a tiny text header followed by raw floats, with rows stored bottom to top
+pub fn write_pfm(mut writer: impl Write, image: &Rgb32FImage) -> io::Result<()> {
+    // a negative scale marks the data as little endian
+    write!(writer, "PF\n{} {}\n-1.0\n", image.width(), image.height())?;
+
+    for row in image.rows().rev() {
+        for pixel in row {
+            for channel in pixel.0 {
+                writer.write_all(&channel.to_le_bytes())?;
+            }
+        }
+    }
+
+    writer.flush()
+}
@@ -0,0 +1,89 @@
+use crate::color::Color;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ToneMapOperator {
+    Clamp,
+    Reinhard,
+    ExtendedReinhard,
+    Aces,
+    Hable,
+}
+
+impl ToneMapOperator {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "clamp" => Some(Self::Clamp),
+            "reinhard" => Some(Self::Reinhard),
+            "extended_reinhard" => Some(Self::ExtendedReinhard),
+            "aces" => Some(Self::Aces),
+            "hable" => Some(Self::Hable),
+            _ => None,
+        }
+    }
+
+    pub fn default_white_point(&self) -> f32 {
+        match self {
+            Self::Clamp | Self::Reinhard | Self::Aces => 1.0,
+            Self::ExtendedReinhard => 4.0,
+            Self::Hable => 11.2,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ToneMapping {
+    pub operator: ToneMapOperator,
+    // in stops, every +1 doubles the brightness
+    pub exposure: f32,
+    // the exposed intensity that ends up as pure white; plain Reinhard and ACES approach white asymptotically and ignore it
+    pub white_point: f32,
+}
+
+impl ToneMapping {
+    const HABLE_EXPOSURE_BIAS: f32 = 2.0;
+
+    pub fn new(operator: ToneMapOperator) -> Self {
+        Self { operator, exposure: 0.0, white_point: operator.default_white_point() }
+    }
+
+    // maps linear scene radiance to linear display values in [0, 1]
+    pub fn apply(&self, color: Color) -> Color {
+        let exposed = color.map(|v| v.max(0.0)) * self.exposure.exp2();
+        let white = self.white_point.max(f32::EPSILON);
+
+        let mapped = match self.operator {
+            ToneMapOperator::Clamp => exposed / white,
+            ToneMapOperator::Reinhard => exposed.map(|v| v / (1.0 + v)),
+            ToneMapOperator::ExtendedReinhard => exposed.map(|v| v * (1.0 + v / (white * white)) / (1.0 + v)),
+            ToneMapOperator::Aces => exposed.map(aces),
+            // with the usual exposure bias of 2 for this curve, so the white point is reached at half its value
+            ToneMapOperator::Hable => exposed.map(|v| hable(Self::HABLE_EXPOSURE_BIAS * v) / hable(white)),
+        };
+
+        mapped.map(|v| v.clamp(0.0, 1.0))
+    }
+}
+
+impl Default for ToneMapping {
+    fn default() -> Self {
+        Self::new(ToneMapOperator::Clamp)
+    }
+}
+
+// Krzysztof Narkowicz's fit of the ACES filmic curve, which expects the input scaled down by 0.6
+fn aces(v: f32) -> f32 {
+    let v = v * 0.6;
+    (v * (2.51 * v + 0.03)) / (v * (2.43 * v + 0.59) + 0.14)
+}
+
+// John Hable's filmic curve from Uncharted 2
+fn hable(v: f32) -> f32 {
+    const A: f32 = 0.15;
+    const B: f32 = 0.50;
+    const C: f32 = 0.10;
+    const D: f32 = 0.20;
+    const E: f32 = 0.02;
+    const F: f32 = 0.30;
+
+    ((v * (A * v + C * B) + D * E) / (v * (A * v + B) + D * F)) - E / F
+}
@@ -0,0 +1,27 @@
+use std::f32::consts::PI;
+use glm::Vec3;
+use crate::pdf::Pdf;
+use crate::sampler::SampleStream;
+use crate::util::onb::Onb;
+use crate::util::warp::cosine_hemisphere;
+
+pub struct CosinePdf {
+    uvw: Onb,
+}
+
+impl CosinePdf {
+    pub fn new(normal: Vec3) -> Self {
+        Self { uvw: Onb::new(normal) }
+    }
+}
+
+impl Pdf for CosinePdf {
+    fn value(&self, direction: Vec3) -> f32 {
+        let cos_theta = direction.normalize().dot(&self.uvw.w());
+        (cos_theta / PI).max(0.0)
+    }
+
+    fn generate(&self, sampler: &mut SampleStream) -> Vec3 {
+        self.uvw.transform(cosine_hemisphere(sampler.get_2d()))
+    }
+}
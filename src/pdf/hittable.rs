@@ -0,0 +1,20 @@
+use glm::Vec3;
+use crate::hittable::Hittable;
+use crate::pdf::Pdf;
+use crate::sampler::SampleStream;
+
+pub struct HittablePdf<'a> {
+    pub objects: &'a dyn Hittable,
+    pub origin: Vec3,
+    pub time: f32,
+}
+
+impl Pdf for HittablePdf<'_> {
+    fn value(&self, direction: Vec3) -> f32 {
+        self.objects.pdf_value(self.origin, direction, self.time)
+    }
+
+    fn generate(&self, sampler: &mut SampleStream) -> Vec3 {
+        self.objects.random(self.origin, self.time, sampler)
+    }
+}
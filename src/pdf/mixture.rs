@@ -0,0 +1,55 @@
+use glm::Vec3;
+use crate::pdf::Pdf;
+use crate::sampler::SampleStream;
+
+pub struct MixturePdf<'a> {
+    pub a: &'a dyn Pdf,
+    pub b: &'a dyn Pdf,
+}
+
+impl Pdf for MixturePdf<'_> {
+    fn value(&self, direction: Vec3) -> f32 {
+        0.5 * self.a.value(direction) + 0.5 * self.b.value(direction)
+    }
+
+    fn generate(&self, sampler: &mut SampleStream) -> Vec3 {
+        if sampler.get_1d() < 0.5 {
+            self.a.generate(sampler)
+        } else {
+            self.b.generate(sampler)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+    use crate::pdf::cosine::CosinePdf;
+    use crate::pdf::sphere::SpherePdf;
+    use crate::sampler::sobol::SobolSampler;
+    use super::*;
+
+    #[test]
+    fn value_is_the_average_of_both_pdfs() {
+        let (cosine, sphere) = (CosinePdf::new(Vec3::z()), SpherePdf);
+        let mixture = MixturePdf { a: &cosine, b: &sphere };
+
+        assert!((mixture.value(Vec3::z()) - 0.5 * (1.0 / PI + 1.0 / (4.0 * PI))).abs() < 1e-6);
+        assert!((mixture.value(-Vec3::z()) - 0.5 / (4.0 * PI)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn generates_from_each_pdf_half_the_time() {
+        let (cosine, sphere) = (CosinePdf::new(Vec3::z()), SpherePdf);
+        let mixture = MixturePdf { a: &cosine, b: &sphere };
+        let sampler = SobolSampler { seed: 5 };
+
+        // only the sphere's half reaches below the cosine lobe's hemisphere, and half of that does
+        let samples = 4096;
+        let below = (0..samples)
+            .filter(|&index| mixture.generate(&mut SampleStream::new(&sampler, 0, 0, index)).z < 0.0)
+            .count();
+
+        assert!((below as f32 / samples as f32 - 0.25).abs() < 0.02, "{below} of {samples} below the horizon");
+    }
+}
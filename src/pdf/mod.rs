@@ -0,0 +1,20 @@
+pub mod cosine;
+pub mod hittable;
+pub mod mixture;
+pub mod sphere;
+
+use glm::Vec3;
+use crate::sampler::SampleStream;
+
+pub trait Pdf {
+    fn value(&self, direction: Vec3) -> f32;
+
+    fn generate(&self, sampler: &mut SampleStream) -> Vec3;
+}
+
+pub fn power_heuristic(pdf: f32, other_pdf: f32) -> f32 {
+    let pdf_squared = pdf * pdf;
+    let sum = pdf_squared + other_pdf * other_pdf;
+
+    if sum > 0.0 { pdf_squared / sum } else { 0.0 }
+}
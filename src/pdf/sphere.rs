@@ -0,0 +1,17 @@
+use std::f32::consts::PI;
+use glm::Vec3;
+use crate::pdf::Pdf;
+use crate::sampler::SampleStream;
+use crate::util::warp::uniform_sphere;
+
+pub struct SpherePdf;
+
+impl Pdf for SpherePdf {
+    fn value(&self, _direction: Vec3) -> f32 {
+        1.0 / (4.0 * PI)
+    }
+
+    fn generate(&self, sampler: &mut SampleStream) -> Vec3 {
+        uniform_sphere(sampler.get_2d())
+    }
+}
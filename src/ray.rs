@@ -6,6 +6,8 @@ use na::Rotation3;
 pub struct Ray {
     pub origin: Vec3,
     pub direction: Vec3,
+    // when the ray was cast, within the camera's shutter interval; moving objects are hit where they are at this time
+    pub time: f32,
 }
 
 impl Ray {
@@ -20,7 +22,8 @@ impl Mul<Ray> for Rotation3<f32> {
     fn mul(self, rhs: Ray) -> Self::Output {
         Ray {
             origin: self * rhs.origin,
-            direction: self * rhs.direction
+            direction: self * rhs.direction,
+            time: rhs.time,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,158 @@
+use std::sync::OnceLock;
+use glm::Vec2;
+use crate::sampler::Sampler;
+use crate::sampler::sobol::{scrambled_sobol_1d, scrambled_sobol_2d};
+use crate::util::hash::{hash, permutation_element};
+
+// every pixel uses the same scrambled Sobol sequence, shifted by a blue noise mask tiled over the image, so neighboring
+// pixels get very different samples and the remaining noise is pushed to high frequencies (Georgiev & Fajardo,
+// "Blue-noise Dithered Sampling"); every dimension reads the mask at a different offset
+pub struct BlueNoiseSampler {
+    seed: u64,
+    mask: &'static [f32],
+}
+
+impl BlueNoiseSampler {
+    const MASK_SIZE: usize = 64;
+    const KERNEL_SIGMA: f32 = 1.5;
+    // fraction of the mask's pixels in the initial pattern of the void and cluster algorithm
+    const INITIAL_DENSITY: f32 = 0.1;
+
+    pub fn new(seed: u64) -> Self {
+        static MASK: OnceLock<Box<[f32]>> = OnceLock::new();
+
+        Self { seed, mask: MASK.get_or_init(void_and_cluster) }
+    }
+
+    fn shift(&self, x: u32, y: u32, dimension: u32) -> f32 {
+        let offset = hash([self.seed, dimension as u64, u64::MAX]);
+
+        let x = (x as usize + offset as usize) % Self::MASK_SIZE;
+        let y = (y as usize + (offset >> 32) as usize) % Self::MASK_SIZE;
+
+        self.mask[y * Self::MASK_SIZE + x]
+    }
+}
+
+impl Sampler for BlueNoiseSampler {
+    fn sample_1d(&self, x: u32, y: u32, index: u32, dimension: u32) -> f32 {
+        let value = scrambled_sobol_1d(index, hash([self.seed, dimension as u64])) + self.shift(x, y, dimension);
+
+        value.fract().min(1.0 - f32::EPSILON)
+    }
+
+    fn sample_2d(&self, x: u32, y: u32, index: u32, dimension: u32) -> Vec2 {
+        let shift = Vec2::new(self.shift(x, y, dimension), self.shift(x, y, dimension + 1));
+        let value = scrambled_sobol_2d(index, hash([self.seed, dimension as u64])) + shift;
+
+        value.map(|n| n.fract().min(1.0 - f32::EPSILON))
+    }
+}
+
+// Ulichney's void and cluster method: ranks every pixel of a tiling mask so that the pixels below any threshold are
+// evenly spread out, normalized to [0, 1)
+fn void_and_cluster() -> Box<[f32]> {
+    let size = BlueNoiseSampler::MASK_SIZE;
+    let pixels = size * size;
+
+    let initial_count = (pixels as f32 * BlueNoiseSampler::INITIAL_DENSITY) as usize;
+    let mut initial = Pattern::new(size);
+
+    for i in 0..initial_count {
+        initial.set(permutation_element(i as u32, pixels as u32, 0) as usize, true);
+    }
+
+    // spread the initial pattern out by moving its tightest cluster into its largest void until that changes nothing
+    for _ in 0..pixels {
+        let cluster = initial.tightest_cluster();
+        initial.set(cluster, false);
+
+        let void = initial.largest_void();
+        initial.set(void, true);
+
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut ranks = vec![0; pixels];
+
+    let mut pattern = initial.clone();
+
+    for rank in (0..initial_count).rev() {
+        let cluster = pattern.tightest_cluster();
+        pattern.set(cluster, false);
+        ranks[cluster] = rank;
+    }
+
+    // filling voids all the way up is the same as removing clusters of the inverted pattern
+    let mut pattern = initial;
+
+    for rank in initial_count..pixels {
+        let void = pattern.largest_void();
+        pattern.set(void, true);
+        ranks[void] = rank;
+    }
+
+    ranks.into_iter()
+        .map(|rank| rank as f32 / pixels as f32)
+        .collect()
+}
+
+#[derive(Clone)]
+struct Pattern {
+    size: usize,
+    set: Vec<bool>,
+    // how crowded each pixel's surroundings are, the sum of a gaussian around every set pixel, wrapping around the edges
+    energy: Vec<f32>,
+    kernel: Vec<f32>,
+}
+
+impl Pattern {
+    fn new(size: usize) -> Self {
+        let kernel = (0..size * size)
+            .map(|i| {
+                let dx = (i % size).min(size - i % size) as f32;
+                let dy = (i / size).min(size - i / size) as f32;
+
+                (-(dx * dx + dy * dy) / (2.0 * BlueNoiseSampler::KERNEL_SIGMA.powi(2))).exp()
+            })
+            .collect();
+
+        Self { size, set: vec![false; size * size], energy: vec![0.0; size * size], kernel }
+    }
+
+    fn set(&mut self, pixel: usize, value: bool) {
+        self.set[pixel] = value;
+
+        let sign = if value { 1.0 } else { -1.0 };
+        let (px, py) = (pixel % self.size, pixel / self.size);
+
+        for (i, energy) in self.energy.iter_mut().enumerate() {
+            let dx = (i % self.size + self.size - px) % self.size;
+            let dy = (i / self.size + self.size - py) % self.size;
+
+            *energy += sign * self.kernel[dy * self.size + dx];
+        }
+    }
+
+    fn tightest_cluster(&self) -> usize {
+        self.extreme(true, f32::gt)
+    }
+
+    fn largest_void(&self) -> usize {
+        self.extreme(false, f32::lt)
+    }
+
+    fn extreme(&self, set: bool, better: fn(&f32, &f32) -> bool) -> usize {
+        let mut best: Option<usize> = None;
+
+        for (i, energy) in self.energy.iter().enumerate() {
+            if self.set[i] == set && best.is_none_or(|best| better(energy, &self.energy[best])) {
+                best = Some(i);
+            }
+        }
+
+        best.expect("patterns are never completely empty or full when searched")
+    }
+}
@@ -0,0 +1,44 @@
+use crate::sampler::Sampler;
+use crate::util::hash::hash_to_unit_f32;
+
+// the radical inverse of the sample's index in a different prime base per dimension, randomly shifted per pixel so
+// neighboring pixels don't repeat the same pattern; dimensions beyond the table fall back to random numbers, since
+// high bases correlate badly anyway
+pub struct HaltonSampler {
+    pub seed: u64,
+}
+
+impl HaltonSampler {
+    const PRIMES: [u32; 32] = [
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53,
+        59, 61, 67, 71, 73, 79, 83, 89, 97, 101, 103, 107, 109, 113, 127, 131,
+    ];
+}
+
+impl Sampler for HaltonSampler {
+    fn sample_1d(&self, x: u32, y: u32, index: u32, dimension: u32) -> f32 {
+        let Some(&base) = Self::PRIMES.get(dimension as usize) else {
+            return hash_to_unit_f32([self.seed, x as u64, y as u64, index as u64, dimension as u64]);
+        };
+
+        let shift = hash_to_unit_f32([self.seed, x as u64, y as u64, dimension as u64]);
+        let value = (radical_inverse(base, index) + shift as f64).fract() as f32;
+
+        value.min(1.0 - f32::EPSILON)
+    }
+}
+
+// mirrors the digits of n in the given base around the radix point
+fn radical_inverse(base: u32, mut n: u32) -> f64 {
+    let inv_base = 1.0 / base as f64;
+    let mut digit_weight = inv_base;
+    let mut value = 0.0;
+
+    while n > 0 {
+        value += (n % base) as f64 * digit_weight;
+        digit_weight *= inv_base;
+        n /= base;
+    }
+
+    value
+}
@@ -0,0 +1,12 @@
+use crate::sampler::Sampler;
+use crate::util::hash::hash_to_unit_f32;
+
+pub struct IndependentSampler {
+    pub seed: u64,
+}
+
+impl Sampler for IndependentSampler {
+    fn sample_1d(&self, x: u32, y: u32, index: u32, dimension: u32) -> f32 {
+        hash_to_unit_f32([self.seed, x as u64, y as u64, index as u64, dimension as u64])
+    }
+}
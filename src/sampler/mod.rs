@@ -0,0 +1,93 @@
+pub mod independent;
+pub mod stratified;
+pub mod halton;
+pub mod sobol;
+pub mod blue_noise;
+
+use glm::Vec2;
+use crate::sampler::blue_noise::BlueNoiseSampler;
+use crate::sampler::halton::HaltonSampler;
+use crate::sampler::independent::IndependentSampler;
+use crate::sampler::sobol::SobolSampler;
+use crate::sampler::stratified::StratifiedSampler;
+
+// coordinates of a pixel's samples, every dimension of every sample is a pure function of the pixel, the sample's index
+// and the dimension, so renders don't depend on which thread takes which sample
+pub trait Sampler: Send + Sync {
+    // in [0, 1)
+    fn sample_1d(&self, x: u32, y: u32, index: u32, dimension: u32) -> f32;
+
+    // in [0, 1)², samplers that stratify pairs of dimensions jointly override this
+    fn sample_2d(&self, x: u32, y: u32, index: u32, dimension: u32) -> Vec2 {
+        Vec2::new(
+            self.sample_1d(x, y, index, dimension),
+            self.sample_1d(x, y, index, dimension + 1),
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SamplerKind {
+    // uniform random numbers
+    Independent,
+    // a jittered grid of samples_per_pixel cells for every dimension
+    Stratified,
+    Halton,
+    // Owen scrambled, with independently shuffled pairs of dimensions
+    #[default]
+    Sobol,
+    // one sequence shared by every pixel, offset by a blue noise mask, so the remaining error looks like fine grain
+    BlueNoise,
+}
+
+impl SamplerKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "independent" | "random" => Some(Self::Independent),
+            "stratified" => Some(Self::Stratified),
+            "halton" => Some(Self::Halton),
+            "sobol" => Some(Self::Sobol),
+            "blue_noise" => Some(Self::BlueNoise),
+            _ => None,
+        }
+    }
+
+    pub fn build(self, seed: u64, samples_per_pixel: u32) -> Box<dyn Sampler> {
+        match self {
+            Self::Independent => Box::new(IndependentSampler { seed }),
+            Self::Stratified => Box::new(StratifiedSampler { seed, samples_per_pixel }),
+            Self::Halton => Box::new(HaltonSampler { seed }),
+            Self::Sobol => Box::new(SobolSampler { seed }),
+            Self::BlueNoise => Box::new(BlueNoiseSampler::new(seed)),
+        }
+    }
+}
+
+// hands out the dimensions of one camera sample in the order they're asked for; the camera takes the first five, a
+// pair for the offset within the pixel, a pair for the lens and one for the time within the shutter interval, the
+// rest go to the integrator for scattering, light selection and so on
+pub struct SampleStream<'a> {
+    sampler: &'a dyn Sampler,
+    x: u32,
+    y: u32,
+    index: u32,
+    dimension: u32,
+}
+
+impl<'a> SampleStream<'a> {
+    pub fn new(sampler: &'a dyn Sampler, x: u32, y: u32, index: u32) -> Self {
+        Self { sampler, x, y, index, dimension: 0 }
+    }
+
+    pub fn get_1d(&mut self) -> f32 {
+        let value = self.sampler.sample_1d(self.x, self.y, self.index, self.dimension);
+        self.dimension += 1;
+        value
+    }
+
+    pub fn get_2d(&mut self) -> Vec2 {
+        let value = self.sampler.sample_2d(self.x, self.y, self.index, self.dimension);
+        self.dimension += 2;
+        value
+    }
+}
@@ -0,0 +1,111 @@
+use glm::Vec2;
+use crate::sampler::Sampler;
+use crate::util::hash::{hash, to_unit_f32};
+
+// the first two Sobol dimensions, Owen scrambled; every pair of dimensions gets its own scramble and shuffled sample
+// order, which keeps each pair well stratified without needing direction numbers for hundreds of dimensions
+// (Burley, "Practical Hash-based Owen Scrambling")
+pub struct SobolSampler {
+    pub seed: u64,
+}
+
+impl Sampler for SobolSampler {
+    fn sample_1d(&self, x: u32, y: u32, index: u32, dimension: u32) -> f32 {
+        let seed = hash([self.seed, x as u64, y as u64, dimension as u64]);
+
+        scrambled_sobol_1d(index, seed)
+    }
+
+    fn sample_2d(&self, x: u32, y: u32, index: u32, dimension: u32) -> Vec2 {
+        let seed = hash([self.seed, x as u64, y as u64, dimension as u64]);
+
+        scrambled_sobol_2d(index, seed)
+    }
+}
+
+pub(super) fn scrambled_sobol_1d(index: u32, seed: u64) -> f32 {
+    let index = owen_scramble(index, seed as u32);
+
+    to_unit_f32(owen_scramble(index.reverse_bits(), (seed >> 32) as u32))
+}
+
+pub(super) fn scrambled_sobol_2d(index: u32, seed: u64) -> Vec2 {
+    let index = owen_scramble(index, seed as u32);
+    let [a, b] = [1, 2].map(|n| (hash([seed, n]) >> 32) as u32);
+
+    Vec2::new(
+        to_unit_f32(owen_scramble(index.reverse_bits(), a)),
+        to_unit_f32(owen_scramble(sobol_second_dimension(index), b)),
+    )
+}
+
+const SECOND_DIMENSION_DIRECTIONS: [u32; 32] = {
+    let mut directions = [0; 32];
+    directions[0] = 1 << 31;
+
+    let mut i = 1;
+    while i < 32 {
+        directions[i] = directions[i - 1] ^ (directions[i - 1] >> 1);
+        i += 1;
+    }
+
+    directions
+};
+
+// the first dimension is just the index's bits reversed
+fn sobol_second_dimension(mut index: u32) -> u32 {
+    let mut value = 0;
+    let mut bit = 0;
+
+    while index != 0 {
+        if index & 1 != 0 {
+            value ^= SECOND_DIMENSION_DIRECTIONS[bit];
+        }
+
+        index >>= 1;
+        bit += 1;
+    }
+
+    value
+}
+
+// randomly flips every bit depending on the bits above it, which keeps a sequence's stratification
+fn owen_scramble(x: u32, seed: u32) -> u32 {
+    laine_karras_permutation(x.reverse_bits(), seed).reverse_bits()
+}
+
+// only lets lower bits affect higher ones
+fn laine_karras_permutation(mut x: u32, seed: u32) -> u32 {
+    x = x.wrapping_add(seed);
+    x ^= x.wrapping_mul(0x6c50_b47c);
+    x ^= x.wrapping_mul(0xb82f_1e52);
+    x ^= x.wrapping_mul(0xc7af_e638);
+    x ^= x.wrapping_mul(0x8d22_f6e6);
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the first 2^k points of a scrambled (0, 2)-sequence put one point in every 2^a by 2^(k - a) box
+    #[test]
+    fn power_of_two_prefixes_are_stratified() {
+        let sampler = SobolSampler { seed: 11 };
+
+        for k in [2, 4, 6] {
+            let count = 1u32 << k;
+            let points: Vec<Vec2> = (0..count).map(|index| sampler.sample_2d(4, 1, index, 6)).collect();
+
+            for a in 0..=k {
+                let (columns, rows) = (1u32 << a, 1u32 << (k - a));
+                let mut cells: Vec<u32> = points.iter()
+                    .map(|point| (point.y * rows as f32) as u32 * columns + (point.x * columns as f32) as u32)
+                    .collect();
+                cells.sort_unstable();
+
+                assert_eq!(cells, (0..count).collect::<Vec<_>>(), "{columns}x{rows} boxes of {count} points");
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use glm::Vec2;
+use crate::sampler::Sampler;
+use crate::util::hash::{hash, hash_to_unit_f32, permutation_element};
+
+// every dimension (or pair of dimensions) of a pixel is split into a grid of samples_per_pixel cells, and each sample
+// gets a random point in a different cell; the cells are shuffled per pixel and dimension so dimensions don't correlate
+pub struct StratifiedSampler {
+    pub seed: u64,
+    pub samples_per_pixel: u32,
+}
+
+impl StratifiedSampler {
+    // which cell of `cells` the sample falls in, or None once there are more samples than cells
+    fn cell(&self, x: u32, y: u32, index: u32, dimension: u32, cells: u32) -> Option<u32> {
+        let seed = hash([self.seed, x as u64, y as u64, dimension as u64]) as u32;
+
+        (index < cells).then(|| permutation_element(index, cells, seed))
+    }
+
+    fn jitter(&self, x: u32, y: u32, index: u32, dimension: u32) -> f32 {
+        hash_to_unit_f32([self.seed, x as u64, y as u64, index as u64, dimension as u64])
+    }
+}
+
+impl Sampler for StratifiedSampler {
+    fn sample_1d(&self, x: u32, y: u32, index: u32, dimension: u32) -> f32 {
+        let jitter = self.jitter(x, y, index, dimension);
+
+        match self.cell(x, y, index, dimension, self.samples_per_pixel) {
+            Some(cell) => ((cell as f32 + jitter) / self.samples_per_pixel as f32).min(1.0 - f32::EPSILON),
+            None => jitter,
+        }
+    }
+
+    fn sample_2d(&self, x: u32, y: u32, index: u32, dimension: u32) -> Vec2 {
+        // as square a grid as samples_per_pixel allows, leaving a few randomly chosen cells empty if it isn't a square
+        let columns = self.samples_per_pixel.isqrt().max(1);
+        let rows = self.samples_per_pixel.div_ceil(columns);
+
+        let jitter = Vec2::new(
+            self.jitter(x, y, index, dimension),
+            self.jitter(x, y, index, dimension + 1),
+        );
+
+        match self.cell(x, y, index, dimension, columns * rows) {
+            Some(cell) => Vec2::new(
+                ((cell % columns) as f32 + jitter.x) / columns as f32,
+                ((cell / columns) as f32 + jitter.y) / rows as f32,
+            ).map(|n| n.min(1.0 - f32::EPSILON)),
+            None => jitter,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_sample_lands_in_its_own_stratum() {
+        for samples_per_pixel in [1, 7, 16] {
+            let sampler = StratifiedSampler { seed: 3, samples_per_pixel };
+
+            for (x, y, dimension) in [(0, 0, 0), (5, 9, 0), (5, 9, 4)] {
+                let mut strata: Vec<u32> = (0..samples_per_pixel)
+                    .map(|index| (sampler.sample_1d(x, y, index, dimension) * samples_per_pixel as f32) as u32)
+                    .collect();
+                strata.sort_unstable();
+
+                assert_eq!(strata, (0..samples_per_pixel).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn pairs_of_samples_land_in_distinct_grid_cells() {
+        // 12 samples make a 3x4 grid, 16 a 4x4 one
+        for (samples_per_pixel, columns, rows) in [(12, 3, 4), (16, 4, 4)] {
+            let sampler = StratifiedSampler { seed: 3, samples_per_pixel };
+
+            let mut cells: Vec<u32> = (0..samples_per_pixel)
+                .map(|index| {
+                    let point = sampler.sample_2d(2, 7, index, 2);
+                    (point.y * rows as f32) as u32 * columns + (point.x * columns as f32) as u32
+                })
+                .collect();
+            cells.sort_unstable();
+
+            assert_eq!(cells, (0..samples_per_pixel).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use rand::rngs::StdRng;
+use crate::prelude::*;
+
+pub fn scene(_rng: &mut StdRng) -> Scene {
+    let mut world = HittableList::default();
+
+    let checker_texture = Arc::new(Lambertian {
+        texture: Arc::new(CheckerTexture::from_colors(
+            0.32,
+            Color::new(0.2, 0.3, 0.1),
+            Color::from_element(0.9),
+        ))
+    });
+
+    world.add(Arc::new(Sphere::new(
+        Vec3::new(0.0, -10.0, 0.0),
+        10.0,
+        checker_texture.clone(),
+    )));
+
+    world.add(Arc::new(Sphere::new(
+        Vec3::new(0.0, 10.0, 0.0),
+        10.0,
+        checker_texture.clone(),
+    )));
+
+    let camera = CameraSettings {
+        center: Vec3::new(13.0, 2.0, 3.0),
+        look_at: Vec3::zeros(),
+        background_color: Color::new(0.70, 0.80, 1.00),
+        dimensions: CameraDimensions::FHD,
+        render_quality: RenderQuality::HIGH,
+        focus_settings: FocusSettings::default(),
+        projection: Projection::Perspective { fov: 20.0 },
+        tone_mapping: ToneMapping::default(),
+        tiles: TileSettings::default(),
+        filter: PixelFilter::default(),
+        aovs: AovSet::default(),
+        shutter: Shutter::default(),
+    };
+
+    let mut bvh = HittableList::default();
+    bvh.add(Arc::new(BVHNode::from(world)));
+
+    Scene { camera, world: bvh, lights: HittableList::default() }
+}
@@ -1,9 +1,11 @@
 use std::num::NonZero;
 use std::sync::Arc;
-use one_weekend_raytracer::prelude::*;
+use rand::rngs::StdRng;
+use crate::prelude::*;
 
-pub fn main() {
+pub fn scene(_rng: &mut StdRng) -> Scene {
     let mut world = HittableList::default();
+    let mut lights = HittableList::default();
 
     let red_mat = Arc::new(Lambertian::from_albedo(
         Color::new(0.65, 0.05, 0.05)
@@ -56,12 +58,15 @@ pub fn main() {
         white_mat.clone()
     )));
 
-    world.add(Arc::new(Quad::new(
+    let light = Arc::new(Quad::new(
         Vec3::new(343.0, 554.0, 332.0),
         Vec3::new(-130.0, 0.0, 0.0),
         Vec3::new(0.0, 0.0, -105.0),
         light_mat
-    )));
+    ));
+
+    world.add(light.clone());
+    lights.add(light);
 
     world.add(Arc::new(Transform::new(
         Arc::new(RectangularPrism::from_opposite_vertices(
@@ -83,17 +88,20 @@ pub fn main() {
         Vec3::new(0.0, 18.0, 0.0),
     )));
 
-    let cam = Camera::new(
-        Vec3::new(278.0, 278.0, -800.0),
-        Vec3::new(278.0, 278.0, 0.0),
-        Color::default(),
-        CameraDimensions::square(NonZero::new(800).expect("nonzero")),
-        RenderQuality::HIGH,
-        FocusSettings::default(),
-        40.0
-    );
-
-    cam.render_screen_par(&world, 20, Some("cornell_box"))
-        .save("cornell_box.png")
-        .expect("failed to save file")
-}
\ No newline at end of file
+    let camera = CameraSettings {
+        center: Vec3::new(278.0, 278.0, -800.0),
+        look_at: Vec3::new(278.0, 278.0, 0.0),
+        background_color: Color::default(),
+        dimensions: CameraDimensions::square(NonZero::new(800).expect("nonzero")),
+        render_quality: RenderQuality::HIGH,
+        focus_settings: FocusSettings::default(),
+        projection: Projection::Perspective { fov: 40.0 },
+        tone_mapping: ToneMapping::default(),
+        tiles: TileSettings::default(),
+        filter: PixelFilter::default(),
+        aovs: AovSet::default(),
+        shutter: Shutter::default(),
+    };
+
+    Scene { camera, world, lights }
+}
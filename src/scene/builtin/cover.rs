@@ -1,8 +1,10 @@
 use std::ops::Range;
 use std::sync::Arc;
-use one_weekend_raytracer::prelude::*;
+use rand::Rng;
+use rand::rngs::StdRng;
+use crate::prelude::*;
 
-fn main() {
+pub fn scene(rng: &mut StdRng) -> Scene {
     let mut world = HittableList::default();
 
     const UNIT_RANGE: Range<f32> = 0.0..1.0f32;
@@ -19,7 +21,7 @@ fn main() {
 
             texture: Arc::new(BlendedTexture {
                 a: Arc::new(SolidColor { albedo: Color::new(0.25, 0.61, 0.04) }),
-                b: Arc::new(NoiseTexture::new(4.0, 4)),
+                b: Arc::new(NoiseTexture::new(4.0, 4, rng)),
                 blend: Arc::new(|a, b|
                     b.x.sqrt() * a
                 ),
@@ -30,22 +32,22 @@ fn main() {
     for a in -11..11 {
         for b in -11..11 {
             let center = Vec3::new(
-                a as f32 + 0.9 * rand::random_range(UNIT_RANGE),
+                a as f32 + 0.9 * rng.random_range(UNIT_RANGE),
                 0.2,
-                b as f32 + 0.9 * rand::random_range(UNIT_RANGE),
+                b as f32 + 0.9 * rng.random_range(UNIT_RANGE),
             );
 
             if (center - Vec3::new(4.0, 0.2, 0.0)).magnitude() > 0.9 {
-                let material: Arc<dyn Material> = match rand::random_range(UNIT_RANGE) {
+                let material: Arc<dyn Material> = match rng.random_range(UNIT_RANGE) {
                     0.0..=0.8 => {
                         Arc::new(Lambertian::from_albedo(
-                            Color::from_fn(|_,_| rand::random_range(UNIT_RANGE) * rand::random_range(UNIT_RANGE))
+                            Color::from_fn(|_,_| rng.random_range(UNIT_RANGE) * rng.random_range(UNIT_RANGE))
                         ))
                     }
                     0.8..=0.95 => {
                         Arc::new(Metal {
-                            albedo: rand_vec::random_vec(0.5..1.0),
-                            fuzz: rand::random_range(0.0..0.5),
+                            albedo: Color::from_fn(|_, _| rng.random_range(0.5..1.0)),
+                            fuzz: rng.random_range(0.0..0.5),
                         })
                     }
                     0.95..=1.0 => {
@@ -84,19 +86,23 @@ fn main() {
         }),
     )));
 
-    let cam = Camera::new(
-        Vec3::new(13.0, 2.0, 3.0),
-        Vec3::zeros(),
-        Color::new(0.70, 0.80, 1.00),
-        CameraDimensions::FHD,
-        RenderQuality::HIGH,
-        FocusSettings { defocus_angle: 0.6, focus_dist: 10.0 },
-        20.0,
-    );
+    let camera = CameraSettings {
+        center: Vec3::new(13.0, 2.0, 3.0),
+        look_at: Vec3::zeros(),
+        background_color: Color::new(0.70, 0.80, 1.00),
+        dimensions: CameraDimensions::FHD,
+        render_quality: RenderQuality::HIGH,
+        focus_settings: FocusSettings { defocus_angle: 0.6, focus_dist: 10.0 },
+        projection: Projection::Perspective { fov: 20.0 },
+        tone_mapping: ToneMapping::default(),
+        tiles: TileSettings::default(),
+        filter: PixelFilter::default(),
+        aovs: AovSet::default(),
+        shutter: Shutter::default(),
+    };
 
-    let world: BVHNode = world.into();
+    let mut bvh = HittableList::default();
+    bvh.add(Arc::new(BVHNode::from(world)));
 
-    cam.render_screen_par(&world, 20, Some("cover"))
-        .save("cover.png")
-        .expect("failed to save file")
-}
\ No newline at end of file
+    Scene { camera, world: bvh, lights: HittableList::default() }
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use rand::rngs::StdRng;
+use crate::prelude::*;
+
+pub fn scene(_rng: &mut StdRng) -> Scene {
+    let mut world = HittableList::default();
+
+    world.add(Arc::new(Sphere::new(
+        Vec3::zeros(),
+        2.0,
+        Arc::new(Lambertian {
+            texture: Arc::new(
+                ImageTexture::open("./assets/earthmap.jpg", ColorSpace::Srgb)
+                    .expect("earth map asset should exist")
+            ),
+        })
+    )));
+
+    let camera = CameraSettings {
+        center: Vec3::new(8.0, 0.0, 12.0),
+        look_at: Vec3::zeros(),
+        background_color: Color::new(0.70, 0.80, 1.00),
+        dimensions: CameraDimensions::FHD,
+        render_quality: RenderQuality::HIGH,
+        focus_settings: FocusSettings::default(),
+        projection: Projection::Perspective { fov: 20.0 },
+        tone_mapping: ToneMapping::default(),
+        tiles: TileSettings::default(),
+        filter: PixelFilter::default(),
+        aovs: AovSet::default(),
+        shutter: Shutter::default(),
+    };
+
+    Scene { camera, world, lights: HittableList::default() }
+}
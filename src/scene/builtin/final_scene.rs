@@ -1,9 +1,12 @@
 use std::num::NonZero;
 use std::sync::Arc;
-use one_weekend_raytracer::prelude::*;
+use rand::Rng;
+use rand::rngs::StdRng;
+use crate::prelude::*;
 
-pub fn main() {
+pub fn scene(rng: &mut StdRng) -> Scene {
     let mut world = HittableList::default();
+    let mut lights = HittableList::default();
 
     let ground_material = Arc::new(
         Lambertian::from_albedo(Color::new(0.48, 0.83, 0.53))
@@ -20,7 +23,7 @@ pub fn main() {
             let z0 = -1000.0 + j as f32 * w;
 
             let x1 = x0 + w;
-            let y1 = rand::random_range(1.0..101.0);
+            let y1 = rng.random_range(1.0..101.0);
             let z1 = z0 + w;
             let prism = RectangularPrism::from_opposite_vertices(
                 Vec3::new(x0, 0.0, z0),
@@ -35,17 +38,21 @@ pub fn main() {
 
     world.add(Arc::new(boxes));
 
-    world.add(Arc::new(Quad::new(
+    let light = Arc::new(Quad::new(
         Vec3::new(123.0, 554.0, 147.0),
         Vec3::new(300.0, 0.0, 0.0),
         Vec3::new(0.0, 0.0, 265.0),
         Arc::new(DiffuseLight::from_emission_color(
             Color::from_element(7.0)
         ))
-    )));
+    ));
 
-    world.add(Arc::new(Sphere::new(
+    world.add(light.clone());
+    lights.add(light);
+
+    world.add(Arc::new(Sphere::moving(
         Vec3::new(400.0, 400.0, 200.0),
+        Vec3::new(430.0, 400.0, 200.0),
         50.0,
         Arc::new(Lambertian::from_albedo(
             Color::new(0.7, 0.3, 0.1)
@@ -95,11 +102,10 @@ pub fn main() {
         Vec3::new(400.0, 200.0, 400.0),
         100.0,
         Arc::new(Lambertian {
-            texture: Arc::new(ImageTexture {
-                image: image::open("./assets/earthmap.jpg")
+            texture: Arc::new(
+                ImageTexture::open("./assets/earthmap.jpg", ColorSpace::Srgb)
                     .expect("earth map asset should exist")
-                    .to_rgb8(),
-            }),
+            ),
         })
     )));
 
@@ -107,7 +113,7 @@ pub fn main() {
         Vec3::new(220.0, 280.0, 300.0),
         80.0,
         Arc::new(Lambertian {
-            texture: Arc::new(NoiseTexture::new(0.2, 7)),
+            texture: Arc::new(NoiseTexture::new(0.2, 7, rng)),
         }),
     )));
 
@@ -117,7 +123,7 @@ pub fn main() {
 
     for _ in 0..1000 {
         small_spheres.add(Arc::new(Sphere::new(
-            Vec3::from_fn(|_, _| rand::random_range(0.0..165.0) ),
+            Vec3::from_fn(|_, _| rng.random_range(0.0..165.0) ),
             10.0,
             white_material.clone()
         )));
@@ -129,17 +135,20 @@ pub fn main() {
         Vec3::new(0.0, 15.0, 0.0),
     )));
 
-    let cam = Camera::new(
-        Vec3::new(478.0, 278.0, -600.0),
-        Vec3::new(278.0, 278.0, 0.0),
-        Color::default(),
-        CameraDimensions::square(NonZero::new(800).expect("nonzero")),
-        RenderQuality { samples_per_pixel: 5000, max_depth: 40 },
-        FocusSettings::default(),
-        40.0
-    );
-
-    cam.render_screen_par(&world, 5, Some("final_scene"))
-        .save("final_scene.png")
-        .expect("failed to save file")
-}
\ No newline at end of file
+    let camera = CameraSettings {
+        center: Vec3::new(478.0, 278.0, -600.0),
+        look_at: Vec3::new(278.0, 278.0, 0.0),
+        background_color: Color::default(),
+        dimensions: CameraDimensions::square(NonZero::new(800).expect("nonzero")),
+        render_quality: RenderQuality { samples_per_pixel: 5000, max_depth: 40, min_depth: 5, adaptive: None, seed: 0, sampler: SamplerKind::Sobol },
+        focus_settings: FocusSettings::default(),
+        projection: Projection::Perspective { fov: 40.0 },
+        tone_mapping: ToneMapping::default(),
+        tiles: TileSettings::default(),
+        filter: PixelFilter::default(),
+        aovs: AovSet::default(),
+        shutter: Shutter { open: 0.0, close: 1.0 },
+    };
+
+    Scene { camera, world, lights }
+}
@@ -0,0 +1,32 @@
+pub mod checkered_spheres;
+pub mod cornell_box;
+pub mod cornell_smoke;
+pub mod cover;
+pub mod earth;
+pub mod final_scene;
+pub mod perlin_spheres;
+pub mod quads;
+pub mod simple_light;
+
+use rand::rngs::StdRng;
+use crate::scene::Scene;
+
+pub type SceneBuilder = fn(&mut StdRng) -> Scene;
+
+pub const BUILTIN_SCENES: [(&str, SceneBuilder); 9] = [
+    ("cover", cover::scene),
+    ("checkered_spheres", checkered_spheres::scene),
+    ("perlin_spheres", perlin_spheres::scene),
+    ("earth", earth::scene),
+    ("quads", quads::scene),
+    ("simple_light", simple_light::scene),
+    ("cornell_box", cornell_box::scene),
+    ("cornell_smoke", cornell_smoke::scene),
+    ("final_scene", final_scene::scene),
+];
+
+pub fn find(name: &str) -> Option<SceneBuilder> {
+    BUILTIN_SCENES.iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, builder)| *builder)
+}
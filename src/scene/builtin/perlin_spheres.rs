@@ -0,0 +1,40 @@
+use std::sync::Arc;
+use rand::rngs::StdRng;
+use crate::prelude::*;
+
+pub fn scene(rng: &mut StdRng) -> Scene {
+    let mut world = HittableList::default();
+
+    let perlin_tex = Arc::new(Lambertian {
+        texture: Arc::new(MarbleTexture::new(4.0, 7, rng)),
+    });
+
+    world.add(Arc::new(Sphere::new(
+        Vec3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        perlin_tex.clone(),
+    )));
+
+    world.add(Arc::new(Sphere::new(
+        Vec3::new(0.0, 2.0, 0.0),
+        2.0,
+        perlin_tex.clone(),
+    )));
+
+    let camera = CameraSettings {
+        center: Vec3::new(13.0, 2.0, 3.0),
+        look_at: Vec3::zeros(),
+        background_color: Color::new(0.70, 0.80, 1.00),
+        dimensions: CameraDimensions::FHD,
+        render_quality: RenderQuality::HIGH,
+        focus_settings: FocusSettings::default(),
+        projection: Projection::Perspective { fov: 20.0 },
+        tone_mapping: ToneMapping::default(),
+        tiles: TileSettings::default(),
+        filter: PixelFilter::default(),
+        aovs: AovSet::default(),
+        shutter: Shutter::default(),
+    };
+
+    Scene { camera, world, lights: HittableList::default() }
+}
@@ -1,8 +1,9 @@
 use std::num::NonZero;
 use std::sync::Arc;
-use one_weekend_raytracer::prelude::*;
+use rand::rngs::StdRng;
+use crate::prelude::*;
 
-pub fn main() {
+pub fn scene(_rng: &mut StdRng) -> Scene {
     let mut world = HittableList::default();
 
     // left
@@ -56,17 +57,20 @@ pub fn main() {
         ))
     )));
 
-    let cam = Camera::new(
-        Vec3::new(0.0, 0.0, 9.0),
-        Vec3::default(),
-        Color::new(0.70, 0.80, 1.00),
-        CameraDimensions::square(NonZero::new(800).expect("nonzero")),
-        RenderQuality::HIGH,
-        FocusSettings::default(),
-        80.0,
-    );
+    let camera = CameraSettings {
+        center: Vec3::new(0.0, 0.0, 9.0),
+        look_at: Vec3::default(),
+        background_color: Color::new(0.70, 0.80, 1.00),
+        dimensions: CameraDimensions::square(NonZero::new(800).expect("nonzero")),
+        render_quality: RenderQuality::HIGH,
+        focus_settings: FocusSettings::default(),
+        projection: Projection::Perspective { fov: 80.0 },
+        tone_mapping: ToneMapping::default(),
+        tiles: TileSettings::default(),
+        filter: PixelFilter::default(),
+        aovs: AovSet::default(),
+        shutter: Shutter::default(),
+    };
 
-    cam.render_screen_par(&world, 20, Some("quads"))
-        .save("quads.png")
-        .expect("failed to save file")
-}
\ No newline at end of file
+    Scene { camera, world, lights: HittableList::default() }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use rand::rngs::StdRng;
+use crate::prelude::*;
+
+pub fn scene(rng: &mut StdRng) -> Scene {
+    let mut world = HittableList::default();
+    let mut lights = HittableList::default();
+
+    let perlin_texture = Arc::new(Lambertian {
+        texture: Arc::new(NoiseTexture::new(4.0, 7, rng)),
+    });
+
+    let red = Arc::new(Lambertian {
+        texture: Arc::new(BlendedTexture {
+            a: Arc::new(SolidColor { albedo: Color::new(0.8, 0.1, 0.04) }),
+            b: Arc::new(NoiseTexture::new(4.0, 4, rng)),
+            blend: Arc::new(|a, b|
+                b.x.sqrt() * a
+            )
+        }),
+    });
+
+    world.add(Arc::new(Sphere::new(
+        Vec3::new(0.0, -1000.0, 0.0), 1000.0, perlin_texture.clone(),
+    )));
+
+    world.add(Arc::new(Sphere::new(
+        Vec3::new(0.0, 2.0, 0.0), 2.0, red.clone(),
+    )));
+
+    let diffuse_light = Arc::new(DiffuseLight::from_emission_color(
+        Color::from_element(4.0),
+    ));
+
+    let quad_light = Arc::new(Quad::new(
+        Vec3::new(3.0, 1.0, -2.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(0.0, 2.0, 0.0),
+        diffuse_light.clone(),
+    ));
+
+    let sphere_light = Arc::new(Sphere::new(
+        Vec3::new(0.0, 7.0, 0.0),
+        2.0,
+        diffuse_light.clone(),
+    ));
+
+    world.add(quad_light.clone());
+    world.add(sphere_light.clone());
+
+    lights.add(quad_light);
+    lights.add(sphere_light);
+
+    let camera = CameraSettings {
+        center: Vec3::new(26.0, 3.0, 6.0),
+        look_at: Vec3::new(0.0, 2.0, 0.0),
+        background_color: Color::default(),
+        dimensions: CameraDimensions::FHD,
+        render_quality: RenderQuality::HIGH,
+        focus_settings: FocusSettings::default(),
+        projection: Projection::Perspective { fov: 20.0 },
+        tone_mapping: ToneMapping::default(),
+        tiles: TileSettings::default(),
+        filter: PixelFilter::default(),
+        aovs: AovSet::default(),
+        shutter: Shutter::default(),
+    };
+
+    Scene { camera, world, lights }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::path::PathBuf;
+use crate::obj::error::ObjError;
+
+#[derive(Debug)]
+pub enum SceneError {
+    Io { path: PathBuf, source: std::io::Error },
+    Parse { line: usize, kind: ParseError },
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    UnterminatedString,
+    MalformedArgument(String),
+    UnknownStatement(String),
+    UnknownKind { statement: String, kind: String },
+    MissingName { statement: String },
+    MissingArgument { key: &'static str },
+    ConflictingArguments { a: &'static str, b: &'static str },
+    UnexpectedArgument(String),
+    InvalidValue { key: String, value: String, expected: &'static str },
+    UnknownTexture(String),
+    UnknownMaterial(String),
+    UnexpectedBlock { statement: String },
+    MissingBlock { statement: String },
+    UnexpectedClose,
+    UnclosedBlock { statement: String },
+    EmptyBlock { statement: String },
+    MisplacedStatement { statement: String, parent: &'static str },
+    Texture { path: PathBuf, source: image::ImageError },
+    Mesh(ObjError),
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Io { path, source } => write!(f, "failed to read '{}': {source}", path.display()),
+            SceneError::Parse { line, kind } => write!(f, "line {line}: {kind}"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnterminatedString => write!(f, "unterminated string"),
+            ParseError::MalformedArgument(arg) => write!(f, "'{arg}' is not of the form 'key=value'"),
+            ParseError::UnknownStatement(statement) => write!(f, "unknown statement '{statement}'"),
+            ParseError::UnknownKind { statement, kind } => write!(f, "unknown {statement} kind '{kind}'"),
+            ParseError::MissingName { statement } => write!(f, "'{statement}' requires a name"),
+            ParseError::MissingArgument { key } => write!(f, "missing required argument '{key}'"),
+            ParseError::ConflictingArguments { a, b } => write!(f, "'{a}' and '{b}' can't be used together"),
+            ParseError::UnexpectedArgument(key) => write!(f, "unexpected argument '{key}'"),
+            ParseError::InvalidValue { key, value, expected } =>
+                write!(f, "invalid value '{value}' for '{key}', expected {expected}"),
+            ParseError::UnknownTexture(name) => write!(f, "texture '{name}' was never defined"),
+            ParseError::UnknownMaterial(name) => write!(f, "material '{name}' was never defined"),
+            ParseError::UnexpectedBlock { statement } => write!(f, "'{statement}' can't open a block"),
+            ParseError::MissingBlock { statement } => write!(f, "'{statement}' must open a block with '{{'"),
+            ParseError::UnexpectedClose => write!(f, "'}}' without a matching block"),
+            ParseError::UnclosedBlock { statement } => write!(f, "'{statement}' block is never closed"),
+            ParseError::EmptyBlock { statement } => write!(f, "'{statement}' block must contain at least one shape"),
+            ParseError::MisplacedStatement { statement, parent } =>
+                write!(f, "'{statement}' must be directly inside a '{parent}' block"),
+            ParseError::Texture { path, source } => write!(f, "failed to load texture '{}': {source}", path.display()),
+            ParseError::Mesh(err) => write!(f, "failed to load mesh: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SceneError::Io { source, .. } => Some(source),
+            SceneError::Parse { kind: ParseError::Texture { source, .. }, .. } => Some(source),
+            SceneError::Parse { kind: ParseError::Mesh(err), .. } => Some(err),
+            SceneError::Parse { .. } => None,
+        }
+    }
+}
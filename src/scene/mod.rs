@@ -0,0 +1,34 @@
+pub mod builtin;
+pub mod error;
+mod parser;
+
+use std::path::Path;
+use crate::camera::Camera;
+use crate::camera::settings::CameraSettings;
+use crate::hittable::hittable_list::HittableList;
+use crate::scene::error::SceneError;
+
+pub struct Scene {
+    pub camera: CameraSettings,
+    pub world: HittableList,
+    pub lights: HittableList,
+}
+
+impl Scene {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SceneError> {
+        let path = path.as_ref();
+
+        let source = std::fs::read_to_string(path)
+            .map_err(|source| SceneError::Io { path: path.to_path_buf(), source })?;
+
+        Self::parse(&source, path.parent().unwrap_or(Path::new("")))
+    }
+
+    pub fn parse(source: &str, base_dir: impl AsRef<Path>) -> Result<Self, SceneError> {
+        parser::parse(source, base_dir.as_ref())
+    }
+
+    pub fn camera(&self) -> Camera {
+        self.camera.build()
+    }
+}
@@ -0,0 +1,1098 @@
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::sync::Arc;
+use glm::Vec3;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use crate::camera::dimensions::CameraDimensions;
+use crate::camera::filter::{FilterKind, FilterMode};
+use crate::camera::model::fisheye::FisheyeMapping;
+use crate::camera::projection::Projection;
+use crate::camera::render_quality::RenderQuality;
+use crate::camera::settings::CameraSettings;
+use crate::camera::shutter::Shutter;
+use crate::camera::tiles::TileOrder;
+use crate::color::{Color, ColorSpace};
+use crate::hittable::{Hittable, edges_span_surface};
+use crate::hittable::bvh::BVHNode;
+use crate::hittable::constant_medium::ConstantMedium;
+use crate::hittable::hittable_list::HittableList;
+use crate::hittable::quad::Quad;
+use crate::hittable::rect_prism::RectangularPrism;
+use crate::hittable::sphere::Sphere;
+use crate::hittable::transform::{Keyframe, Transform};
+use crate::hittable::triangle::Triangle;
+use crate::material::Material;
+use crate::material::dielectric::Dielectric;
+use crate::material::diffuse_light::DiffuseLight;
+use crate::material::isotropic::Isotropic;
+use crate::material::lambertian::Lambertian;
+use crate::material::metal::Metal;
+use crate::obj;
+use crate::output::tone_map::{ToneMapOperator, ToneMapping};
+use crate::sampler::SamplerKind;
+use crate::scene::Scene;
+use crate::scene::error::{ParseError, SceneError};
+use crate::texture::Texture;
+use crate::texture::checker::CheckerTexture;
+use crate::texture::image::ImageTexture;
+use crate::texture::perlin::{MarbleTexture, NoiseTexture};
+use crate::texture::solid_color::SolidColor;
+
+const NOISE_SEED: u64 = 0;
+
+pub(super) fn parse(source: &str, base_dir: &Path) -> Result<Scene, SceneError> {
+    let mut parser = Parser {
+        base_dir,
+        camera: CameraSettings::default(),
+        textures: HashMap::new(),
+        materials: HashMap::new(),
+        blocks: Vec::new(),
+        world: HittableList::default(),
+        lights: HittableList::default(),
+        rng: StdRng::seed_from_u64(NOISE_SEED),
+        line: 0,
+    };
+
+    for (i, line) in source.lines().enumerate() {
+        parser.line = i + 1;
+
+        parser.parse_line(line)
+            .map_err(|kind| SceneError::Parse { line: i + 1, kind })?;
+    }
+
+    if let Some(block) = parser.blocks.pop() {
+        return Err(SceneError::Parse {
+            line: block.line,
+            kind: ParseError::UnclosedBlock { statement: block.statement },
+        });
+    }
+
+    Ok(Scene { camera: parser.camera, world: parser.world, lights: parser.lights })
+}
+
+struct Parser<'a> {
+    base_dir: &'a Path,
+    camera: CameraSettings,
+    textures: HashMap<String, Arc<dyn Texture>>,
+    materials: HashMap<String, Arc<dyn Material>>,
+    blocks: Vec<Block>,
+    world: HittableList,
+    lights: HittableList,
+    // only used to build noise textures, seeded the same every time so a file always describes the same scene
+    rng: StdRng,
+    line: usize,
+}
+
+struct Block {
+    statement: String,
+    line: usize,
+    kind: BlockKind,
+    children: HittableList,
+    // the emissive shapes among the children, and their children's
+    lights: HittableList,
+}
+
+enum BlockKind {
+    Transform { keyframes: Vec<Keyframe> },
+    Medium { density: f32, texture: Arc<dyn Texture> },
+    Bvh,
+}
+
+impl Parser<'_> {
+    fn parse_line(&mut self, line: &str) -> Result<(), ParseError> {
+        let tokens = tokenize(line)?;
+
+        let Some((keyword, rest)) = tokens.split_first() else {
+            return Ok(());
+        };
+
+        if keyword == "}" && rest.is_empty() {
+            return self.close_block();
+        }
+
+        let mut stmt = Statement::new(keyword, rest)?;
+
+        // emissive meshes are sampled as lights group by group, as only some of their materials may be emissive
+        let mut lights = HittableList::default();
+
+        let object: Arc<dyn Hittable> = match keyword.as_str() {
+            "camera" => return self.parse_camera(stmt),
+            "dimensions" => return self.parse_dimensions(stmt),
+            "quality" => return self.parse_quality(stmt),
+            "focus" => return self.parse_focus(stmt),
+            "tonemap" => return self.parse_tone_map(stmt),
+            "tiles" => return self.parse_tiles(stmt),
+            "filter" => return self.parse_filter(stmt),
+            "shutter" => return self.parse_shutter(stmt),
+            "texture" => return self.parse_texture(stmt),
+            "material" => return self.parse_material(stmt),
+            "transform" | "medium" | "bvh" => return self.open_block(stmt),
+            "keyframe" => return self.parse_keyframe(stmt),
+            "sphere" => {
+                let center = stmt.required("center")?;
+                let radius = stmt.required_positive("radius")?;
+                let end_center = stmt.optional("moves_to")?.unwrap_or(center);
+                let material = self.material(&mut stmt)?;
+
+                Arc::new(Sphere::moving(center, end_center, radius, material))
+            }
+            "quad" => {
+                let q = stmt.required("q")?;
+                let u: Vec3 = stmt.required("u")?;
+                let v: Vec3 = stmt.required("v")?;
+                let material = self.material(&mut stmt)?;
+
+                if !edges_span_surface(u, v) {
+                    return Err(ParseError::InvalidValue {
+                        key: "v".into(),
+                        value: format_vec(v),
+                        expected: "an edge that isn't parallel to 'u'",
+                    });
+                }
+
+                Arc::new(Quad::new(q, u, v, material))
+            }
+            "box" => {
+                let a: Vec3 = stmt.required("a")?;
+                let b: Vec3 = stmt.required("b")?;
+                let material = self.material(&mut stmt)?;
+
+                if a.iter().zip(b.iter()).any(|(a, b)| a == b) {
+                    return Err(ParseError::InvalidValue {
+                        key: "b".into(),
+                        value: format_vec(b),
+                        expected: "a corner that differs from 'a' along every axis",
+                    });
+                }
+
+                Arc::new(RectangularPrism::from_opposite_vertices(a, b, material))
+            }
+            "triangle" => {
+                let vertices = [stmt.required("a")?, stmt.required("b")?, stmt.required("c")?];
+                let material = self.material(&mut stmt)?;
+
+                let triangle = Triangle::new(vertices, material).map_err(|_| ParseError::InvalidValue {
+                    key: "c".into(),
+                    value: format_vec(vertices[2]),
+                    expected: "a vertex that isn't collinear with 'a' and 'b'",
+                })?;
+
+                Arc::new(triangle)
+            }
+            "mesh" => {
+                let path = self.base_dir.join(stmt.required::<String>("path")?);
+
+                let mesh = match stmt.optional::<String>("material")? {
+                    Some(name) => obj::load_with_default_material(path, self.named_material(name)?),
+                    None => obj::load(path),
+                };
+
+                let mesh = mesh.map_err(ParseError::Mesh)?;
+
+                mesh.objects.iter()
+                    .filter(|group| is_emissive(group.as_ref()))
+                    .for_each(|group| lights.add(group.clone()));
+
+                Arc::new(mesh)
+            }
+            _ => return Err(ParseError::UnknownStatement(keyword.clone())),
+        };
+
+        stmt.finish()?;
+
+        // emissive shapes are sampled directly as lights
+        if matches!(keyword.as_str(), "sphere" | "quad" | "triangle" | "box") && is_emissive(object.as_ref()) {
+            lights.add(object.clone());
+        }
+
+        self.add_object(object, lights);
+
+        Ok(())
+    }
+
+    // to the enclosing block, or the scene itself
+    fn add_object(&mut self, object: Arc<dyn Hittable>, lights: HittableList) {
+        let (children, parent_lights) = match self.blocks.last_mut() {
+            Some(block) => (&mut block.children, &mut block.lights),
+            None => (&mut self.world, &mut self.lights),
+        };
+
+        children.add(object);
+        lights.objects.into_iter().for_each(|light| parent_lights.add(light));
+    }
+
+    fn parse_camera(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        if let Some(center) = stmt.optional("center")? {
+            self.camera.center = center;
+        }
+
+        if let Some(look_at) = stmt.optional("look_at")? {
+            self.camera.look_at = look_at;
+        }
+
+        // switching the projection resets its parameters, unless they're given too
+        if let Some(name) = stmt.optional::<String>("projection")? {
+            self.camera.projection = Projection::from_name(&name).ok_or_else(|| ParseError::InvalidValue {
+                key: "projection".into(),
+                value: name,
+                expected: "one of 'perspective', 'orthographic', 'equirectangular', 'cubemap' or 'fisheye'",
+            })?;
+        }
+
+        if let Some(fov) = stmt.optional_positive("fov")? {
+            match &mut self.camera.projection {
+                Projection::Perspective { .. } if fov >= 180.0 => return Err(ParseError::InvalidValue {
+                    key: "fov".into(),
+                    value: fov.to_string(),
+                    expected: "an angle of less than 180 degrees",
+                }),
+                Projection::Perspective { fov: current } => *current = fov,
+                Projection::Fisheye { .. } if fov > 360.0 => return Err(ParseError::InvalidValue {
+                    key: "fov".into(),
+                    value: fov.to_string(),
+                    expected: "an angle of at most 360 degrees",
+                }),
+                Projection::Fisheye { fov: current, .. } => *current = fov,
+                _ => return Err(ParseError::UnexpectedArgument("fov".into())),
+            }
+        }
+
+        if let Some(name) = stmt.optional::<String>("mapping")? {
+            let Projection::Fisheye { mapping: current, .. } = &mut self.camera.projection else {
+                return Err(ParseError::UnexpectedArgument("mapping".into()));
+            };
+
+            *current = FisheyeMapping::from_name(&name).ok_or_else(|| ParseError::InvalidValue {
+                key: "mapping".into(),
+                value: name,
+                expected: "either 'equidistant' or 'equisolid'",
+            })?;
+        }
+
+        if let Some(view_height) = stmt.optional_positive("view_height")? {
+            let Projection::Orthographic { view_height: current } = &mut self.camera.projection else {
+                return Err(ParseError::UnexpectedArgument("view_height".into()));
+            };
+
+            *current = view_height;
+        }
+
+        if let Some(background) = stmt.optional_color("background")? {
+            self.camera.background_color = background;
+        }
+
+        stmt.finish()
+    }
+
+    fn parse_dimensions(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        if let Some(preset) = stmt.optional::<String>("preset")? {
+            self.camera.dimensions = match preset.as_str() {
+                "medium" => CameraDimensions::MEDIUM,
+                "large" => CameraDimensions::LARGE,
+                "fhd" => CameraDimensions::FHD,
+                _ => return Err(ParseError::InvalidValue {
+                    key: "preset".into(),
+                    value: preset,
+                    expected: "one of 'medium', 'large' or 'fhd'",
+                }),
+            };
+
+            return stmt.finish();
+        }
+
+        let width = stmt.required::<NonZeroU32>("width")?;
+
+        self.camera.dimensions = match (stmt.optional::<NonZeroU32>("height")?, stmt.optional_positive("aspect")?) {
+            (Some(_), Some(_)) => return Err(ParseError::ConflictingArguments { a: "height", b: "aspect" }),
+            (Some(height), None) => CameraDimensions { width, height },
+            (None, Some(aspect)) => CameraDimensions::from_aspect_ratio(width, aspect),
+            (None, None) => CameraDimensions::square(width),
+        };
+
+        stmt.finish()
+    }
+
+    fn parse_quality(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        if let Some(preset) = stmt.optional::<String>("preset")? {
+            self.camera.render_quality = match preset.as_str() {
+                "low" => RenderQuality::LOW,
+                "medium" => RenderQuality::MEDIUM,
+                "high" => RenderQuality::HIGH,
+                _ => return Err(ParseError::InvalidValue {
+                    key: "preset".into(),
+                    value: preset,
+                    expected: "one of 'low', 'medium' or 'high'",
+                }),
+            };
+        }
+
+        if let Some(samples) = stmt.optional::<NonZeroU32>("samples")? {
+            self.camera.render_quality.samples_per_pixel = samples.get();
+        }
+
+        if let Some(depth) = stmt.optional("depth")? {
+            self.camera.render_quality.max_depth = depth;
+        }
+
+        if let Some(depth) = stmt.optional("min_depth")? {
+            self.camera.render_quality.min_depth = depth;
+        }
+
+        if let Some(seed) = stmt.optional("seed")? {
+            self.camera.render_quality.seed = seed;
+        }
+
+        if let Some(name) = stmt.optional::<String>("sampler")? {
+            self.camera.render_quality.sampler = SamplerKind::from_name(&name).ok_or_else(|| ParseError::InvalidValue {
+                key: "sampler".into(),
+                value: name,
+                expected: "one of 'independent', 'stratified', 'halton', 'sobol' or 'blue_noise'",
+            })?;
+        }
+
+        let min_samples = stmt.optional::<u32>("min_samples")?;
+        let error_threshold = stmt.optional_positive("adaptive_error")?;
+
+        if min_samples.is_some() || error_threshold.is_some() {
+            let adaptive = self.camera.render_quality.adaptive.get_or_insert_default();
+
+            if let Some(min_samples) = min_samples {
+                adaptive.min_samples_per_pixel = min_samples;
+            }
+
+            if let Some(error_threshold) = error_threshold {
+                adaptive.error_threshold = error_threshold;
+            }
+        }
+
+        stmt.finish()
+    }
+
+    fn parse_tone_map(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        if let Some(name) = stmt.optional::<String>("operator")? {
+            let operator = ToneMapOperator::from_name(&name).ok_or_else(|| ParseError::InvalidValue {
+                key: "operator".into(),
+                value: name,
+                expected: "one of 'clamp', 'reinhard', 'extended_reinhard', 'aces' or 'hable'",
+            })?;
+
+            self.camera.tone_mapping = ToneMapping::new(operator);
+        }
+
+        if let Some(exposure) = stmt.optional("exposure")? {
+            self.camera.tone_mapping.exposure = exposure;
+        }
+
+        if let Some(white_point) = stmt.optional_positive("white")? {
+            self.camera.tone_mapping.white_point = white_point;
+        }
+
+        stmt.finish()
+    }
+
+    fn parse_tiles(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        if let Some(size) = stmt.optional("size")? {
+            self.camera.tiles.size = size;
+        }
+
+        if let Some(name) = stmt.optional::<String>("order")? {
+            self.camera.tiles.order = TileOrder::from_name(&name).ok_or_else(|| ParseError::InvalidValue {
+                key: "order".into(),
+                value: name,
+                expected: "one of 'scanline', 'spiral' or 'hilbert'",
+            })?;
+        }
+
+        stmt.finish()
+    }
+
+    fn parse_filter(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        // changing the kind also changes the radius to the kind's default, unless one is given
+        if let Some(name) = stmt.optional::<String>("kind")? {
+            let kind = FilterKind::from_name(&name).ok_or_else(|| ParseError::InvalidValue {
+                key: "kind".into(),
+                value: name,
+                expected: "one of 'box', 'tent', 'gaussian', 'mitchell' or 'lanczos'",
+            })?;
+
+            self.camera.filter.kind = kind;
+            self.camera.filter.radius = kind.default_radius();
+        }
+
+        if let Some(radius) = stmt.optional_positive("radius")? {
+            self.camera.filter.radius = radius;
+        }
+
+        if let Some(name) = stmt.optional::<String>("mode")? {
+            self.camera.filter.mode = FilterMode::from_name(&name).ok_or_else(|| ParseError::InvalidValue {
+                key: "mode".into(),
+                value: name,
+                expected: "either 'importance' or 'splat'",
+            })?;
+        }
+
+        stmt.finish()
+    }
+
+    fn parse_shutter(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        let open = stmt.optional("open")?.unwrap_or(0.0);
+        let close: f32 = stmt.required("close")?;
+
+        if close < open {
+            return Err(ParseError::InvalidValue {
+                key: "close".into(),
+                value: close.to_string(),
+                expected: "a time no earlier than 'open'",
+            });
+        }
+
+        self.camera.shutter = Shutter { open, close };
+
+        stmt.finish()
+    }
+
+    fn parse_focus(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        if let Some(angle) = stmt.optional("angle")? {
+            self.camera.focus_settings.defocus_angle = angle;
+        }
+
+        if let Some(distance) = stmt.optional_positive("distance")? {
+            self.camera.focus_settings.focus_dist = distance;
+        }
+
+        stmt.finish()
+    }
+
+    fn parse_texture(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        let (name, kind) = stmt.name_and_kind()?;
+
+        let texture: Arc<dyn Texture> = match kind.as_str() {
+            "solid" => Arc::new(SolidColor { albedo: stmt.required_color("color")? }),
+            "checker" => {
+                let scale = stmt.required_positive("scale")?;
+                let even = self.texture(&mut stmt, "even")?;
+                let odd = self.texture(&mut stmt, "odd")?;
+
+                Arc::new(CheckerTexture::from_textures(scale, even, odd))
+            }
+            "image" => {
+                let path = self.base_dir.join(stmt.required::<String>("path")?);
+
+                let color_space = match stmt.optional::<String>("color_space")?.as_deref() {
+                    None | Some("srgb") => ColorSpace::Srgb,
+                    Some("linear") => ColorSpace::Linear,
+                    Some(other) => return Err(ParseError::InvalidValue {
+                        key: "color_space".into(),
+                        value: other.to_string(),
+                        expected: "either 'srgb' or 'linear'",
+                    }),
+                };
+
+                let texture = ImageTexture::open(&path, color_space)
+                    .map_err(|source| ParseError::Texture { path, source })?;
+
+                Arc::new(texture)
+            }
+            "noise" => Arc::new(NoiseTexture::new(
+                stmt.required_positive("freq")?,
+                stmt.optional("depth")?.unwrap_or(7),
+                &mut self.rng,
+            )),
+            "marble" => Arc::new(MarbleTexture::new(
+                stmt.required_positive("freq")?,
+                stmt.optional("depth")?.unwrap_or(7),
+                &mut self.rng,
+            )),
+            _ => return Err(ParseError::UnknownKind { statement: "texture".into(), kind }),
+        };
+
+        stmt.finish()?;
+
+        self.textures.insert(name, texture);
+
+        Ok(())
+    }
+
+    fn parse_material(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        let (name, kind) = stmt.name_and_kind()?;
+
+        let material: Arc<dyn Material> = match kind.as_str() {
+            "lambertian" => Arc::new(Lambertian { texture: self.color_or_texture(&mut stmt, "albedo")? }),
+            "metal" => Arc::new(Metal {
+                albedo: stmt.required_color("albedo")?,
+                fuzz: stmt.optional("fuzz")?.unwrap_or(0.0),
+            }),
+            "dielectric" => Arc::new(Dielectric { refraction_index: stmt.required_positive("ior")? }),
+            "light" => Arc::new(DiffuseLight { texture: self.color_or_texture(&mut stmt, "emit")? }),
+            "isotropic" => Arc::new(Isotropic { texture: self.color_or_texture(&mut stmt, "albedo")? }),
+            _ => return Err(ParseError::UnknownKind { statement: "material".into(), kind }),
+        };
+
+        stmt.finish()?;
+
+        self.materials.insert(name, material);
+
+        Ok(())
+    }
+
+    fn open_block(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        let kind = match stmt.keyword.as_str() {
+            "transform" => BlockKind::Transform { keyframes: vec![keyframe(&mut stmt, 0.0)?] },
+            "medium" => BlockKind::Medium {
+                density: stmt.required_positive("density")?,
+                texture: self.color_or_texture(&mut stmt, "albedo")?,
+            },
+            _ => BlockKind::Bvh,
+        };
+
+        if !stmt.opens_block {
+            return Err(ParseError::MissingBlock { statement: stmt.keyword });
+        }
+
+        stmt.opens_block = false;
+
+        let statement = stmt.keyword.clone();
+
+        stmt.finish()?;
+
+        self.blocks.push(Block {
+            statement,
+            line: self.line,
+            kind,
+            children: HittableList::default(),
+            lights: HittableList::default(),
+        });
+
+        Ok(())
+    }
+
+    fn close_block(&mut self) -> Result<(), ParseError> {
+        let Block { statement, kind, children, lights, .. } = self.blocks.pop().ok_or(ParseError::UnexpectedClose)?;
+
+        if children.objects.is_empty() {
+            return Err(ParseError::EmptyBlock { statement });
+        }
+
+        // lights move along with the rest of a transform's children, and a medium's boundary doesn't emit anything
+        let (object, lights): (Arc<dyn Hittable>, HittableList) = match kind {
+            BlockKind::Transform { keyframes } => {
+                let mut transformed_lights = HittableList::default();
+
+                if !lights.objects.is_empty() {
+                    transformed_lights.add(Arc::new(Transform::keyframed(single_or_list(lights), &keyframes)));
+                }
+
+                (Arc::new(Transform::keyframed(single_or_list(children), &keyframes)), transformed_lights)
+            }
+            BlockKind::Medium { density, texture } =>
+                (Arc::new(ConstantMedium::new_isotropic(single_or_list(children), density, texture)), HittableList::default()),
+            BlockKind::Bvh => (Arc::new(BVHNode::from(children)), lights),
+        };
+
+        self.add_object(object, lights);
+
+        Ok(())
+    }
+
+    // adds a pose to the transform block it's directly in
+    fn parse_keyframe(&mut self, mut stmt: Statement) -> Result<(), ParseError> {
+        let Some(Block { kind: BlockKind::Transform { keyframes }, .. }) = self.blocks.last_mut() else {
+            return Err(ParseError::MisplacedStatement { statement: stmt.keyword, parent: "transform" });
+        };
+
+        let time = stmt.required("time")?;
+
+        if keyframes.iter().any(|keyframe| keyframe.time == time) {
+            return Err(ParseError::InvalidValue {
+                key: "time".into(),
+                value: time.to_string(),
+                expected: "a time no other keyframe of the transform has",
+            });
+        }
+
+        keyframes.push(keyframe(&mut stmt, time)?);
+
+        stmt.finish()
+    }
+
+    fn material(&self, stmt: &mut Statement) -> Result<Arc<dyn Material>, ParseError> {
+        self.named_material(stmt.required("material")?)
+    }
+
+    fn named_material(&self, name: String) -> Result<Arc<dyn Material>, ParseError> {
+        self.materials.get(&name).cloned().ok_or(ParseError::UnknownMaterial(name))
+    }
+
+    fn texture(&self, stmt: &mut Statement, key: &'static str) -> Result<Arc<dyn Texture>, ParseError> {
+        let value = stmt.required::<String>(key)?;
+
+        // values that look like colors are solid textures, anything else must be a named texture
+        match parse_color(&value) {
+            Some(albedo) => Ok(Arc::new(SolidColor { albedo })),
+            None => self.textures.get(&value).cloned().ok_or(ParseError::UnknownTexture(value)),
+        }
+    }
+
+    fn color_or_texture(&self, stmt: &mut Statement, color_key: &'static str) -> Result<Arc<dyn Texture>, ParseError> {
+        match (stmt.optional_color(color_key)?, stmt.optional::<String>("texture")?) {
+            (Some(_), Some(_)) => Err(ParseError::ConflictingArguments { a: color_key, b: "texture" }),
+            (Some(albedo), None) => Ok(Arc::new(SolidColor { albedo })),
+            (None, Some(name)) => self.textures.get(&name).cloned().ok_or(ParseError::UnknownTexture(name)),
+            (None, None) => Err(ParseError::MissingArgument { key: color_key }),
+        }
+    }
+}
+
+struct Statement {
+    keyword: String,
+    positional: Vec<String>,
+    args: Vec<(String, String)>,
+    opens_block: bool,
+}
+
+impl Statement {
+    fn new(keyword: &str, tokens: &[String]) -> Result<Self, ParseError> {
+        let mut stmt = Self {
+            keyword: keyword.to_string(),
+            positional: Vec::new(),
+            args: Vec::new(),
+            opens_block: false,
+        };
+
+        for (i, token) in tokens.iter().enumerate() {
+            if token == "{" && i == tokens.len() - 1 {
+                stmt.opens_block = true;
+            } else if let Some((key, value)) = token.split_once('=') {
+                if key.is_empty() || value.is_empty() {
+                    return Err(ParseError::MalformedArgument(token.clone()));
+                }
+
+                stmt.args.push((key.to_string(), value.to_string()));
+            } else {
+                stmt.positional.push(token.clone());
+            }
+        }
+
+        Ok(stmt)
+    }
+
+    fn name_and_kind(&mut self) -> Result<(String, String), ParseError> {
+        if self.positional.len() < 2 {
+            return Err(ParseError::MissingName { statement: self.keyword.clone() });
+        }
+
+        let kind = self.positional.remove(1);
+        let name = self.positional.remove(0);
+
+        Ok((name, kind))
+    }
+
+    fn take(&mut self, key: &'static str) -> Option<String> {
+        let i = self.args.iter().position(|(k, _)| k == key)?;
+        Some(self.args.remove(i).1)
+    }
+
+    fn optional<T: Value>(&mut self, key: &'static str) -> Result<Option<T>, ParseError> {
+        self.take(key)
+            .map(|value| T::parse(&value).ok_or(ParseError::InvalidValue {
+                key: key.to_string(),
+                value,
+                expected: T::EXPECTED,
+            }))
+            .transpose()
+    }
+
+    fn required<T: Value>(&mut self, key: &'static str) -> Result<T, ParseError> {
+        self.optional(key)?.ok_or(ParseError::MissingArgument { key })
+    }
+
+    fn optional_positive(&mut self, key: &'static str) -> Result<Option<f32>, ParseError> {
+        match self.optional::<f32>(key)? {
+            Some(n) if n <= 0.0 => Err(ParseError::InvalidValue {
+                key: key.to_string(),
+                value: n.to_string(),
+                expected: "a positive number",
+            }),
+            n => Ok(n),
+        }
+    }
+
+    fn required_positive(&mut self, key: &'static str) -> Result<f32, ParseError> {
+        self.optional_positive(key)?.ok_or(ParseError::MissingArgument { key })
+    }
+
+    fn optional_color(&mut self, key: &'static str) -> Result<Option<Color>, ParseError> {
+        self.take(key)
+            .map(|value| parse_color(&value).ok_or(ParseError::InvalidValue {
+                key: key.to_string(),
+                value,
+                expected: "a color, either 'r,g,b' or a single value",
+            }))
+            .transpose()
+    }
+
+    fn required_color(&mut self, key: &'static str) -> Result<Color, ParseError> {
+        self.optional_color(key)?.ok_or(ParseError::MissingArgument { key })
+    }
+
+    fn finish(self) -> Result<(), ParseError> {
+        if self.opens_block {
+            return Err(ParseError::UnexpectedBlock { statement: self.keyword });
+        }
+
+        match (self.positional.first(), self.args.first()) {
+            (Some(token), _) => Err(ParseError::UnexpectedArgument(token.clone())),
+            (None, Some((key, _))) => Err(ParseError::UnexpectedArgument(key.clone())),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+trait Value: Sized {
+    const EXPECTED: &'static str;
+
+    fn parse(value: &str) -> Option<Self>;
+}
+
+impl Value for f32 {
+    const EXPECTED: &'static str = "a number";
+
+    fn parse(value: &str) -> Option<Self> {
+        value.parse().ok().filter(|n: &f32| n.is_finite())
+    }
+}
+
+impl Value for u8 {
+    const EXPECTED: &'static str = "an integer in [0, 255]";
+
+    fn parse(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
+impl Value for u16 {
+    const EXPECTED: &'static str = "an integer in [0, 65535]";
+
+    fn parse(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
+impl Value for u32 {
+    const EXPECTED: &'static str = "a non-negative integer";
+
+    fn parse(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
+impl Value for u64 {
+    const EXPECTED: &'static str = "a non-negative integer";
+
+    fn parse(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
+impl Value for NonZeroU32 {
+    const EXPECTED: &'static str = "a positive integer";
+
+    fn parse(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
+impl Value for Vec3 {
+    const EXPECTED: &'static str = "a vector of the form 'x,y,z'";
+
+    fn parse(value: &str) -> Option<Self> {
+        let components = value.split(',')
+            .map(<f32 as Value>::parse)
+            .collect::<Option<Vec<_>>>()?;
+
+        <[f32; 3]>::try_from(components).ok().map(Vec3::from)
+    }
+}
+
+impl Value for String {
+    const EXPECTED: &'static str = "a string";
+
+    fn parse(value: &str) -> Option<Self> {
+        Some(value.to_string())
+    }
+}
+
+fn keyframe(stmt: &mut Statement, default_time: f32) -> Result<Keyframe, ParseError> {
+    Ok(Keyframe {
+        time: stmt.optional("time")?.unwrap_or(default_time),
+        translation: stmt.optional("translate")?.unwrap_or_default(),
+        angles: stmt.optional("rotate")?.unwrap_or_default(),
+    })
+}
+
+fn is_emissive(object: &dyn Hittable) -> bool {
+    let mut emissive = false;
+    object.visit_materials(&mut |material| emissive |= material.is_emissive());
+    emissive
+}
+
+fn single_or_list(list: HittableList) -> Arc<dyn Hittable> {
+    match list.objects.as_slice() {
+        [only] => only.clone(),
+        _ => Arc::new(list),
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    <Vec3 as Value>::parse(value)
+        .or_else(|| <f32 as Value>::parse(value).map(Color::from_element))
+}
+
+fn format_vec(v: Vec3) -> String {
+    format!("{},{},{}", v.x, v.y, v.z)
+}
+
+// splits on whitespace outside of quotes, and strips the quotes; '#' starts a comment
+fn tokenize(line: &str) -> Result<Vec<String>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut in_quotes = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_token = true;
+            }
+            '#' if !in_quotes => break,
+            c if c.is_whitespace() && !in_quotes => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(ParseError::UnterminatedString);
+    }
+
+    if in_token {
+        tokens.push(current);
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORNELL: &str = r#"
+# comments and blank lines are skipped
+camera center=278,278,-800 look_at=278,278,0 fov=40 background=0
+dimensions width=64 height=32
+shutter open=0 close=1
+
+material white lambertian albedo=0.73
+material "warm light" light emit=15,14,13
+
+quad q=0,0,0 u=555,0,0 v=0,0,555 material=white
+quad q=343,554,332 u=-130,0,0 v=0,0,-105 material="warm light"
+sphere center=190,90,190 radius=90 moves_to=190,120,190 material=white
+
+transform translate=265,0,295 rotate=0,-15,0 {
+    box a=0,0,0 b=165,330,165 material=white
+    keyframe time=1 translate=265,10,295 rotate=0,-30,0
+}
+"#;
+
+    fn parse_str(source: &str) -> Result<Scene, SceneError> {
+        parse(source, Path::new(""))
+    }
+
+    // the line the error was reported on, and what it was
+    fn parse_error(source: &str) -> (usize, ParseError) {
+        match parse_str(source) {
+            Ok(_) => panic!("expected the scene to fail to parse"),
+            Err(SceneError::Parse { line, kind }) => (line, kind),
+            Err(err) => panic!("expected a parse error, got {err}"),
+        }
+    }
+
+    #[test]
+    fn parses_settings_and_shapes() {
+        let scene = parse_str(CORNELL).expect("scene should parse");
+
+        assert_eq!(scene.camera.center, Vec3::new(278.0, 278.0, -800.0));
+        assert_eq!(scene.camera.look_at, Vec3::new(278.0, 278.0, 0.0));
+        assert_eq!(scene.camera.projection, Projection::Perspective { fov: 40.0 });
+        assert_eq!((scene.camera.dimensions.width.get(), scene.camera.dimensions.height.get()), (64, 32));
+        assert_eq!(scene.camera.shutter, Shutter { open: 0.0, close: 1.0 });
+
+        assert_eq!(scene.world.objects.len(), 4);
+        assert_eq!(scene.lights.objects.len(), 1);
+    }
+
+    #[test]
+    fn parses_the_example_scenes() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("scenes");
+
+        for entry in std::fs::read_dir(directory).expect("scenes directory should exist") {
+            let path = entry.expect("scenes directory should be readable").path();
+
+            if let Err(err) = Scene::load(&path) {
+                panic!("{}: {err}", path.display());
+            }
+        }
+    }
+
+    #[test]
+    fn later_statements_override_earlier_ones() {
+        let scene = parse_str("camera fov=40\ncamera projection=orthographic view_height=5\n").expect("scene should parse");
+
+        assert_eq!(scene.camera.projection, Projection::Orthographic { view_height: 5.0 });
+    }
+
+    #[test]
+    fn registers_emissive_shapes_in_blocks_as_lights() {
+        let scene = parse_str(
+            "material light light emit=4\n\
+             material white lambertian albedo=0.5\n\
+             transform translate=0,1,0 {\n\
+                 box a=0,0,0 b=1,1,1 material=light\n\
+                 sphere center=3,0,0 radius=1 material=light\n\
+                 sphere center=6,0,0 radius=1 material=white\n\
+             }\n\
+             bvh {\n\
+                 triangle a=0,0,0 b=1,0,0 c=0,1,0 material=light\n\
+             }\n\
+             medium density=0.1 albedo=1 {\n\
+                 sphere center=0,0,0 radius=10 material=light\n\
+             }\n",
+        ).expect("scene should parse");
+
+        // the transform's lights are wrapped together, and a medium's boundary isn't a light
+        assert_eq!(scene.world.objects.len(), 3);
+        assert_eq!(scene.lights.objects.len(), 2);
+    }
+
+    #[test]
+    fn accepts_small_shapes() {
+        parse_str(
+            "material white lambertian albedo=0.5\n\
+             quad q=0,0,0 u=0.001,0,0 v=0,0.001,0 material=white\n\
+             triangle a=0,0,0 b=0.0001,0,0 c=0,0.0001,0 material=white\n",
+        ).expect("small but valid shapes should parse");
+    }
+
+    #[test]
+    fn reports_the_line_of_the_error() {
+        let (line, kind) = parse_error("camera fov=40\n\n# comment\nteapot size=3\n");
+
+        assert_eq!(line, 4);
+        assert!(matches!(kind, ParseError::UnknownStatement(statement) if statement == "teapot"));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(matches!(parse_error("camera fov=").1, ParseError::MalformedArgument(arg) if arg == "fov="));
+        assert!(matches!(parse_error("camera fov").1, ParseError::UnexpectedArgument(arg) if arg == "fov"));
+        assert!(matches!(parse_error("material \"white lambertian albedo=1").1, ParseError::UnterminatedString));
+        assert!(matches!(parse_error("camera fov=40 fov=50").1, ParseError::UnexpectedArgument(key) if key == "fov"));
+        assert!(matches!(parse_error("camera zoom=2").1, ParseError::UnexpectedArgument(key) if key == "zoom"));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(matches!(parse_error("camera fov=-10").1, ParseError::InvalidValue { key, .. } if key == "fov"));
+        assert!(matches!(parse_error("camera fov=180").1, ParseError::InvalidValue { key, .. } if key == "fov"));
+        assert!(matches!(parse_error("camera fov=270").1, ParseError::InvalidValue { key, .. } if key == "fov"));
+        assert!(matches!(
+            parse_error("camera projection=fisheye fov=400").1,
+            ParseError::InvalidValue { key, .. } if key == "fov"
+        ));
+        assert!(parse_str("camera projection=fisheye fov=270").is_ok());
+        assert!(matches!(parse_error("camera center=1,2").1, ParseError::InvalidValue { key, .. } if key == "center"));
+        assert!(matches!(
+            parse_error("dimensions width=10 height=10 aspect=2").1,
+            ParseError::ConflictingArguments { a: "height", b: "aspect" }
+        ));
+        assert!(matches!(
+            parse_error("camera projection=orthographic fov=40").1,
+            ParseError::UnexpectedArgument(key) if key == "fov"
+        ));
+    }
+
+    #[test]
+    fn rejects_degenerate_shapes() {
+        let (line, kind) = parse_error("material white lambertian albedo=0.5\nquad q=0,0,0 u=1,0,0 v=2,0,0 material=white");
+        assert_eq!(line, 2);
+        assert!(matches!(kind, ParseError::InvalidValue { key, .. } if key == "v"));
+
+        let (line, kind) = parse_error("material white lambertian albedo=0.5\ntriangle a=0,0,0 b=1,1,1 c=3,3,3 material=white");
+        assert_eq!(line, 2);
+        assert!(matches!(kind, ParseError::InvalidValue { key, .. } if key == "c"));
+
+        let (line, kind) = parse_error("material white lambertian albedo=0.5\nbox a=0,0,0 b=1,1,0 material=white");
+        assert_eq!(line, 2);
+        assert!(matches!(kind, ParseError::InvalidValue { key, .. } if key == "b"));
+    }
+
+    #[test]
+    fn rejects_unknown_references() {
+        assert!(matches!(parse_error("sphere center=0,0,0 radius=1").1, ParseError::MissingArgument { key: "material" }));
+        assert!(matches!(
+            parse_error("sphere center=0,0,0 radius=1 material=chrome").1,
+            ParseError::UnknownMaterial(name) if name == "chrome"
+        ));
+        assert!(matches!(
+            parse_error("material floor lambertian texture=checker").1,
+            ParseError::UnknownTexture(name) if name == "checker"
+        ));
+        assert!(matches!(
+            parse_error("material floor plastic albedo=1").1,
+            ParseError::UnknownKind { statement, kind } if statement == "material" && kind == "plastic"
+        ));
+    }
+
+    #[test]
+    fn rejects_unbalanced_blocks() {
+        let unclosed = "material white lambertian albedo=0.5\ntransform translate=1,0,0 {\nsphere center=0,0,0 radius=1 material=white";
+        let (line, kind) = parse_error(unclosed);
+        assert_eq!(line, 2, "an unclosed block is reported where it opens");
+        assert!(matches!(kind, ParseError::UnclosedBlock { statement } if statement == "transform"));
+
+        assert!(matches!(parse_error("camera fov=40\n}").1, ParseError::UnexpectedClose));
+        assert!(matches!(parse_error("bvh {\n}").1, ParseError::EmptyBlock { statement } if statement == "bvh"));
+        assert!(matches!(parse_error("transform translate=1,0,0").1, ParseError::MissingBlock { statement } if statement == "transform"));
+        assert!(matches!(parse_error("camera fov=40 {").1, ParseError::UnexpectedBlock { statement } if statement == "camera"));
+    }
+
+    #[test]
+    fn keyframes_must_be_directly_in_a_transform() {
+        let source = "material white lambertian albedo=0.5\nbvh {\nkeyframe time=1 translate=1,0,0\n}";
+        let (line, kind) = parse_error(source);
+
+        assert_eq!(line, 3);
+        assert!(matches!(kind, ParseError::MisplacedStatement { statement, parent: "transform" } if statement == "keyframe"));
+
+        let duplicate = "transform translate=0,0,0 {\nkeyframe time=0 translate=1,0,0\n}";
+        assert!(matches!(parse_error(duplicate).1, ParseError::InvalidValue { key, .. } if key == "time"));
+    }
+
+    #[test]
+    fn error_messages_include_the_line() {
+        let err = parse_str("camera fov=40\nmaterial white lambertian").err().expect("scene should fail to parse");
+
+        assert_eq!(err.to_string(), "line 2: missing required argument 'albedo'");
+    }
+}
@@ -1,10 +1,35 @@
+use std::path::Path;
 use glm::{Vec2, Vec3};
-use image::RgbImage;
-use crate::color::Color;
+use image::{ImageResult, Rgb, Rgb32FImage, RgbImage};
+use crate::color::{Color, ColorSpace, srgb_to_linear};
 use crate::texture::Texture;
 
 pub struct ImageTexture {
-    pub image: RgbImage,
+    // always linear, sRGB images are decoded when the texture is created
+    image: Rgb32FImage,
+}
+
+impl ImageTexture {
+    pub fn new(image: &RgbImage, color_space: ColorSpace) -> Self {
+        let decode: [f32; 256] = std::array::from_fn(|i| {
+            let v = i as f32 / 255.0;
+
+            match color_space {
+                ColorSpace::Srgb => srgb_to_linear(v),
+                ColorSpace::Linear => v,
+            }
+        });
+
+        Self {
+            image: Rgb32FImage::from_fn(image.width(), image.height(), |x, y| {
+                Rgb(image.get_pixel(x, y).0.map(|c| decode[c as usize]))
+            }),
+        }
+    }
+
+    pub fn open(path: impl AsRef<Path>, color_space: ColorSpace) -> ImageResult<Self> {
+        Ok(Self::new(&image::open(path)?.to_rgb8(), color_space))
+    }
 }
 
 impl Texture for ImageTexture {
@@ -17,6 +42,6 @@ impl Texture for ImageTexture {
             ((v * self.image.height() as f32) as u32).clamp(0, self.image.height() -1),
         );
 
-        Color::from_row_slice(&pixel.0.map(|c| c as f32)) * 1.0 / 255.0
+        Color::from(pixel.0)
     }
-}
\ No newline at end of file
+}
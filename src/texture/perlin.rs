@@ -1,4 +1,5 @@
 use glm::{Vec2, Vec3};
+use rand::Rng;
 use crate::color::Color;
 use crate::texture::Texture;
 
@@ -9,25 +10,19 @@ pub struct Perlin<const RES: usize> {
     perm_z: [i32; RES],
 }
 
-impl<const RES: usize> Default for Perlin<RES> {
-    fn default() -> Self {
+impl<const RES: usize> Perlin<RES> {
+    pub fn new(rng: &mut impl Rng) -> Self {
         let rand_vec = std::array::from_fn(|_| {
-            Vec3::from_fn(|_,_| rand::random_range(-1.0..1.0) )
+            Vec3::from_fn(|_,_| rng.random_range(-1.0..1.0) )
         });
 
         Self {
             rand_vec,
-            perm_x: Self::generate_perm(),
-            perm_y: Self::generate_perm(),
-            perm_z: Self::generate_perm(),
+            perm_x: Self::generate_perm(rng),
+            perm_y: Self::generate_perm(rng),
+            perm_z: Self::generate_perm(rng),
         }
     }
-}
-
-impl<const RES: usize> Perlin<RES> {
-    pub fn new() -> Self {
-        Self::default()
-    }
 
     pub fn turbulence(&self, mut p: Vec3, freq: f32, depth: u8) -> f32 {
         let mut acc = 0.0;
@@ -91,11 +86,11 @@ impl<const RES: usize> Perlin<RES> {
         acc
     }
 
-    fn generate_perm() -> [i32; RES] {
+    fn generate_perm(rng: &mut impl Rng) -> [i32; RES] {
         let mut p = std::array::from_fn(|i| i as i32);
 
         for i in (1..RES).rev() {
-            p.swap(i, rand::random_range(0..=i));
+            p.swap(i, rng.random_range(0..=i));
         }
 
         p
@@ -109,11 +104,11 @@ pub struct NoiseTexture {
 }
 
 impl NoiseTexture {
-    pub fn new(freq: f32, depth: u8) -> Self {
+    pub fn new(freq: f32, depth: u8, rng: &mut impl Rng) -> Self {
         assert!(freq > 0.0, "frequency must be positive");
 
         Self {
-            noise: Perlin::new(),
+            noise: Perlin::new(rng),
             freq,
             depth,
         }
@@ -133,9 +128,9 @@ pub struct MarbleTexture {
 }
 
 impl MarbleTexture {
-    pub fn new(freq: f32, depth: u8) -> Self {
+    pub fn new(freq: f32, depth: u8, rng: &mut impl Rng) -> Self {
         Self {
-            noise: Perlin::new(),
+            noise: Perlin::new(rng),
             freq,
             depth,
         }
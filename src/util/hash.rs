@@ -0,0 +1,58 @@
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+// combines values into one well mixed hash, used to derive independent seeds and random numbers without any state
+pub fn hash(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(GOLDEN_GAMMA, |hash, value| mix(hash.wrapping_add(GOLDEN_GAMMA) ^ value))
+}
+
+// splitmix64 finalizer
+pub fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// maps the top 24 bits to [0, 1), so the result can't round up to 1
+pub fn to_unit_f32(bits: u32) -> f32 {
+    (bits >> 8) as f32 / (1u32 << 24) as f32
+}
+
+pub fn hash_to_unit_f32(values: impl IntoIterator<Item = u64>) -> f32 {
+    to_unit_f32((hash(values) >> 32) as u32)
+}
+
+// the position of i in a pseudo-random permutation of 0..len chosen by seed (Kensler, "Correlated Multi-Jittered Sampling")
+pub fn permutation_element(mut i: u32, len: u32, seed: u32) -> u32 {
+    let mut w = len - 1;
+    w |= w >> 1;
+    w |= w >> 2;
+    w |= w >> 4;
+    w |= w >> 8;
+    w |= w >> 16;
+
+    loop {
+        i ^= seed;
+        i = i.wrapping_mul(0xe170_893d);
+        i ^= seed >> 16;
+        i ^= (i & w) >> 4;
+        i ^= seed >> 8;
+        i = i.wrapping_mul(0x0929_eb3f);
+        i ^= seed >> 23;
+        i ^= (i & w) >> 1;
+        i = i.wrapping_mul(1 | seed >> 27);
+        i = i.wrapping_mul(0x6935_fa69);
+        i ^= (i & w) >> 11;
+        i = i.wrapping_mul(0x74dc_b303);
+        i ^= (i & w) >> 2;
+        i = i.wrapping_mul(0x9e50_1cc3);
+        i ^= (i & w) >> 2;
+        i = i.wrapping_mul(0xc860_a3df);
+        i &= w;
+        i ^= i >> 5;
+
+        // cycle walk until the permutation of the power of two range lands inside 0..len
+        if i < len {
+            return (i + seed) % len;
+        }
+    }
+}
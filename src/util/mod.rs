@@ -1,2 +1,5 @@
 pub mod vec3_random;
-pub mod timer;
\ No newline at end of file
+pub mod timer;
+pub mod onb;
+pub mod hash;
+pub mod warp;
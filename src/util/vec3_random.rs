@@ -2,9 +2,7 @@ use glm::Vec3;
 use rand::distr::uniform::SampleRange;
 use rand::Rng;
 
-pub fn random_vec(range: impl SampleRange<f32> + Clone) -> Vec3 {
-    let mut rng = rand::rng();
-
+pub fn random_vec(range: impl SampleRange<f32> + Clone, rng: &mut impl Rng) -> Vec3 {
     Vec3::new(
         rng.random_range(range.clone()),
         rng.random_range(range.clone()),
@@ -12,9 +10,9 @@ pub fn random_vec(range: impl SampleRange<f32> + Clone) -> Vec3 {
     )
 }
 
-pub fn random_vec_in_unit_sphere() -> Vec3 {
+pub fn random_vec_in_unit_sphere(rng: &mut impl Rng) -> Vec3 {
     loop {
-        let v = random_vec(-1.0..=1.0f32);
+        let v = random_vec(-1.0..=1.0f32, rng);
 
         if v.magnitude_squared() < 1.0 {
             return v;
@@ -22,8 +20,8 @@ pub fn random_vec_in_unit_sphere() -> Vec3 {
     }
 }
 
-pub fn random_vec_on_hemisphere(normal: Vec3) -> Vec3 {
-    let on_unit_sphere = random_vec_in_unit_sphere().normalize();
+pub fn random_vec_on_hemisphere(normal: Vec3, rng: &mut impl Rng) -> Vec3 {
+    let on_unit_sphere = random_vec_in_unit_sphere(rng).normalize();
 
     if on_unit_sphere.dot(&normal) > 0.0 {
         on_unit_sphere
@@ -32,9 +30,7 @@ pub fn random_vec_on_hemisphere(normal: Vec3) -> Vec3 {
     }
 }
 
-pub fn random_vec_in_unit_disk() -> Vec3 {
-    let mut rng = rand::rng();
-
+pub fn random_vec_in_unit_disk(rng: &mut impl Rng) -> Vec3 {
     loop {
         let v = Vec3::new(
             rng.random_range(-1.0..=1.0f32),
@@ -46,4 +42,7 @@ pub fn random_vec_in_unit_disk() -> Vec3 {
             return v;
         }
     }
-}
\ No newline at end of file
+}
+pub fn random_unit_vec(rng: &mut impl Rng) -> Vec3 {
+    random_vec_in_unit_sphere(rng).normalize()
+}
@@ -0,0 +1,46 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, TAU};
+use glm::{Vec2, Vec3};
+
+// these map uniform samples in [0, 1)² onto other domains without rejection, so the samples' stratification carries over
+
+pub fn uniform_sphere(u: Vec2) -> Vec3 {
+    let z = 1.0 - 2.0 * u.x;
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    let phi = TAU * u.y;
+
+    Vec3::new(r * phi.cos(), r * phi.sin(), z)
+}
+
+// Shirley & Chiu's concentric mapping, which keeps neighboring samples close together on the disk
+pub fn concentric_disk(u: Vec2) -> Vec2 {
+    let offset = 2.0 * u - Vec2::from_element(1.0);
+
+    if offset == Vec2::zeros() {
+        return Vec2::zeros();
+    }
+
+    let (r, theta) = if offset.x.abs() > offset.y.abs() {
+        (offset.x, FRAC_PI_4 * (offset.y / offset.x))
+    } else {
+        (offset.y, FRAC_PI_2 - FRAC_PI_4 * (offset.x / offset.y))
+    };
+
+    r * Vec2::new(theta.cos(), theta.sin())
+}
+
+pub fn cosine_hemisphere(u: Vec2) -> Vec3 {
+    let d = concentric_disk(u);
+    let z = (1.0 - d.magnitude_squared()).max(0.0).sqrt();
+
+    Vec3::new(d.x, d.y, z)
+}
+
+// directions towards a sphere of the given radius, centered distance_squared away along +z
+pub fn uniform_cone_to_sphere(radius: f32, distance_squared: f32, u: Vec2) -> Vec3 {
+    let z = 1.0 + u.y * ((1.0 - radius * radius / distance_squared).max(0.0).sqrt() - 1.0);
+
+    let phi = TAU * u.x;
+    let sin_theta = (1.0 - z * z).max(0.0).sqrt();
+
+    Vec3::new(phi.cos() * sin_theta, phi.sin() * sin_theta, z)
+}